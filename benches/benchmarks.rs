@@ -1,10 +1,12 @@
 use criterion::{criterion_main, criterion_group, Criterion};
 use log::{LevelFilter};
-use env_logger;
 
 mod symbollang;
 mod customlang;
 mod egraph;
+mod phases;
+mod time_to_load;
+mod report;
 
 
 fn init_logger() {
@@ -23,10 +25,30 @@ criterion_group!(benches, init_benchmarks);
 criterion_main! {
     benches,
     symbollang::basic_maths::benches,
+    symbollang::bitvector::benches,
     symbollang::calc_logic::benches,
+    symbollang::calculus::benches,
+    symbollang::circuits::benches,
+    symbollang::lambda::benches,
+    symbollang::matrix::benches,
     symbollang::prop_logic::benches,
+    symbollang::reachability::benches,
+    symbollang::relational::benches,
+    symbollang::variadic_maths::benches,
+    symbollang::while_superinterpreter::benches,
     customlang::basic_maths::benches,
+    customlang::bitvector::benches,
     customlang::calc_logic::benches,
+    customlang::calculus::benches,
+    customlang::circuits::benches,
+    customlang::lambda::benches,
+    customlang::matrix::benches,
     customlang::prop_logic::benches,
+    customlang::reachability::benches,
+    customlang::relational::benches,
+    customlang::variadic_maths::benches,
+    customlang::while_superinterpreter::benches,
     egraph::benches,
+    phases::benches,
+    time_to_load::benches,
 }
@@ -0,0 +1,22 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::{inputs, optimize};
+use egg_benchmark::bitvector::*;
+use egg_benchmark::customlang::bitvector::*;
+use crate::report::bench_cost;
+
+pub fn bitvector_benchmark(c: &mut Criterion) {
+    let rules = bitvector_rules();
+
+    for (name, src) in inputs::bitvector() {
+        let expr: RecExpr<BitVec> = src.parse().unwrap();
+        // the quality of the result, as latency before and after
+        bench_cost(c, &format!("customlang/bitvector/{}", name), || {
+            let (_, cost, size) = optimize(black_box(&expr), black_box(&rules), 10, |_| Latency);
+            (cost, size)
+        });
+    }
+}
+
+criterion_group!(benches, bitvector_benchmark);
+criterion_main!(benches);
@@ -1,108 +1,35 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use egg::*;
 use egg_benchmark::*;
-use log::{warn};
+use egg_benchmark::inputs;
+use egg_benchmark::customlang::calc_logic::*;
+use crate::report::bench_size;
 
-// ## Theory of Calculational Logic
-// https://www.cs.cornell.edu/gries/Logic/Axioms.html
-// The axioms of calculational propositional logic C are listed in the order in
-// which they are usually presented and taught. Note that equivalence comes
-// first. Note also that, after the first axiom, we take advantage of
-// associativity of equivalence and write sequences of equivalences without
-// parentheses. We use == for equivalence, | for disjunction, & for conjunction,
-//
-// Golden rule: p & q == p == q == p | q
-//
-// Implication: p ⟹ q == p | q == q
-// Consequence: p ⟸q == q ⟹ p
-//
-// Definition of false: false == !true
-
-define_language! {
-    pub enum CalcLogic {
-        Num(i32),
-        "==" = Eq([Id; 2]),
-        "||" = Or([Id; 2]),
-        "!!" = Not([Id; 1]),
-        "!=" = NotEq([Id; 2]),
-        "&&" = And([Id; 2]),
-        "=>" = Impl([Id; 2]),
-        Symbol(Symbol),
-    }
-}
-
-pub fn calc_logic_rules() -> Vec<Rewrite<CalcLogic, ()>> {
-    vec![
-        // ((p == q) == r) == (p == (q == r))      # Associativity of ==:
-        rewrite!("==-assoc"; "(== (== ?p ?q) ?r)" <=> "(== ?p (== ?q ?r))"),
-        // (p == q) == (q == p)                    # Symmetry of ==:
-        rewrite!("==-sym"; "(== ?p ?q)" <=> "(== ?q ?p)"),
-        // !(p == q) == (!(p) == q)                # Distributivity of !:
-        rewrite!("==-distr"; "(!! (== ?p ?q))" <=> "(== (!! ?p) ?q)"),
-        // (p != q) == !(p == q)                   # Definition of !=:
-        rewrite!("!="; "(!= ?p ?q)" <=> "(!! (== ?p ?q))"),
-        // ((p || q) || r) == (p || (q || r))      # Associativity of ||:
-        rewrite!("||-assoc"; "(|| (|| ?p ?q) ?r)" <=> "(|| ?p (|| ?q ?r))"),
-        // (p || q) == (q || p)                    # Symmetry of ||:
-        rewrite!("||-sym"; "(|| ?p ?q)" <=> "(|| ?q ?p)"),
-        // (p || (q == r)) == (p || q == p || r)   # Distributivity of ||:
-        rewrite!("||-distr"; "(|| ?p (== ?q ?r))" <=> "(== (|| ?p ?q) (|| ?p ?r))"),
-        // !(p || q) == (!p && !q)                 # DeMorgan
-        rewrite!("||-demorgan"; "(!! (|| ?p ?q))" <=> "(&& (!! ?p) (!! ?q))"),
-        // !(p && q) == (!p || !q)
-        rewrite!("!&&"; "(!! (&& ?p ?q))" <=> "(|| (!! ?p) (!! ?q))"),
-        // (p && q) == ((p == q) == p || q)
-        rewrite!("&&"; "(&& ?p ?q)" <=> "(== (== ?p ?q) (|| ?p ?q))"),
-        // (p ⟹  q) == ((p || q) == q)
-        rewrite!("=>"; "(=> ?p ?q)" <=> "(== (|| ?p ?q) ?q)"),
-        // (q == q) --> true                       # Identity of ==:
-        vec![rewrite!("==-id"; "(== ?p ?p)" => "true")],
-        // (p || p) --> p                          # Idempotency of ||:
-        vec![rewrite!("||-idem"; "(|| ?p ?p)" => "?p")],
-        // (p || !(p)) --> true                    # Excluded Middle:
-        vec![rewrite!("||-excl"; "(|| ?p (!! ?p))" => "true")],
-    ]
-    .concat()
-}
-
-// fold = @theory p q begin
-//   (p::Bool == q::Bool) => (p == q)
-//   (p::Bool || q::Bool) => (p || q)
-//   (p::Bool ⟹ q::Bool)  => ((p || q) == q)
-//   (p::Bool && q::Bool) => (p && q)
-//   !(p::Bool)           => (!p)
-// end
-//
-// calculational_logic_theory = calc ∪ fold
-
-pub fn calc_logic_benchmark(c: &mut Criterion) {
-    let rules = calc_logic_rules();
+fn bench_theory<N: Analysis<CalcLogic> + Default>(c: &mut Criterion, theory: &str, rules: &Vec<Rewrite<CalcLogic, N>>) {
     let tru: RecExpr<CalcLogic> = "true".parse().unwrap();
 
-    let demorgan: RecExpr<CalcLogic> = "(== (!! (|| p q)) (&& (!! p) (!! q)))".parse().unwrap();
-    c.bench_function("customlang/calc_logic/demorgan", |b| {
-        let mut size = EGraphSize{num_classes:0, num_nodes:0, num_memo:0};
-        b.iter(|| {
-            let (res,itersize) = prove(black_box(&demorgan), black_box(&rules), 1, 10, &tru);
-            size = itersize;
-            assert!(tru.eq(&res))
-        });
-        warn!("customlang/calc_logic/demorgan {}", size);
+    let demorgan: RecExpr<CalcLogic> = inputs::demorgan().parse().unwrap();
+    bench_size(c, &format!("customlang/{}/demorgan", theory), || {
+        let (res, size) = prove(black_box(&demorgan), black_box(rules), 1, 10, &tru);
+        assert!(tru.eq(&res), "not proved: {}", to_julia(&res));
+        size
     });
 
-    let frege: RecExpr<CalcLogic> = "(=> (=> p (=> q r)) (=> (=> p q) (=> p r)))"
-        .parse()
-        .unwrap();
-    c.bench_function("customlang/calc_logic/freges_theorem", |b| {
-        let mut size = EGraphSize{num_classes:0, num_nodes:0, num_memo:0};
-        b.iter(|| {
-            let (res,itersize) = prove(black_box(&frege), black_box(&rules), 2, 10, &tru);
-            size = itersize;
-            assert!(tru.eq(&res))
-        });
-        warn!("customlang/calc_logic/freges_theorem {}", size);
+    let frege: RecExpr<CalcLogic> = inputs::freges_theorem().parse().unwrap();
+    bench_size(c, &format!("customlang/{}/freges_theorem", theory), || {
+        let (res, size) = prove(black_box(&frege), black_box(rules), 2, 10, &tru);
+        assert!(tru.eq(&res), "not proved: {}", to_julia(&res));
+        size
     });
 }
 
+pub fn calc_logic_benchmark(c: &mut Criterion) {
+    // calc alone, and calculational_logic_theory = calc ∪ fold as in Metatheory.jl
+    bench_theory(c, "calc_logic", &calc_logic_rules());
+    bench_theory(c, "calc_logic_fold", &calculational_logic_rules());
+    // calc with sort checks on every match
+    bench_theory(c, "calc_logic_sorted", &calc_logic_sorted_rules());
+}
+
 criterion_group!(benches, calc_logic_benchmark);
 criterion_main!(benches);
@@ -16,6 +16,7 @@ use std::char;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use egg::{*};
 use egg_benchmark::*;
+use egg_benchmark::customlang::variadic_maths::VariadicMath;
 use log::{warn};
 
 define_language! {
@@ -49,6 +50,13 @@ fn nested_expr(level: u32) -> String {
     }
 }
 
+fn size_of<L: Language>(egraph: &EGraph<L, ()>) -> EGraphSize {
+    EGraphSize {
+        num_classes: egraph.classes().count(),
+        num_memo: egraph.total_size(),
+        num_nodes: egraph.total_number_of_nodes() }
+}
+
 pub fn egraph_benchmark(c: &mut Criterion) {
 
     c.bench_function("egraph/constructor",
@@ -72,6 +80,24 @@ pub fn egraph_benchmark(c: &mut Criterion) {
             warn!("egraph/addexpr {}", size);
         }
     );
+
+    let binary_size = size_of(&Runner::<BasicMath,()>::default().with_expr(&expr).egraph);
+    let expr: RecExpr<VariadicMath> = variadic::flatten(&expr.to_string().parse().unwrap());
+    c.bench_function( "egraph/addexpr_variadic",
+        |b| {
+            let mut size = EGraphSize{num_classes:0, num_nodes:0, num_memo:0};
+            b.iter(|| {
+                let runner: Runner<VariadicMath,()> = Runner::default().with_expr(black_box(&expr));
+                size = size_of(&runner.egraph);
+                runner
+            });
+            warn!("egraph/addexpr_variadic {}", size);
+            warn!(
+                "egraph/addexpr_variadic vs binary: classes {} vs {}, nodes {} vs {}",
+                size.num_classes, binary_size.num_classes, size.num_nodes, binary_size.num_nodes
+            );
+        }
+    );
 }
 
 criterion_group!(benches, egraph_benchmark);
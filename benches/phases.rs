@@ -0,0 +1,65 @@
+// Times the phases of `simplify` separately: parsing the input, building the
+// rules, adding the input to a fresh e-graph, running equality saturation and
+// extracting the best term. Every phase gets its input from an untimed setup,
+// so the rows add up to one `simplify` call plus parsing and rule
+// construction. The `SymbolLang` and `define_language!` variants go through
+// the same function and therefore have the same phase boundaries.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use egg::*;
+use egg_benchmark::inputs::{self, WHILE_10};
+use egg_benchmark::{customlang, simplify_runner, symbollang};
+
+fn bench_phases<L, N>(c: &mut Criterion, name: &str, src: &str, rules: fn() -> Vec<Rewrite<L, N>>, timeout: usize)
+where
+    L: Language + FromOp,
+    N: Analysis<L> + Default,
+{
+    c.bench_function(&format!("{}/parse", name), |b| {
+        b.iter(|| black_box(src).parse::<RecExpr<L>>().unwrap())
+    });
+    c.bench_function(&format!("{}/rules", name), |b| {
+        b.iter_with_large_drop(rules)
+    });
+
+    let expr: RecExpr<L> = src.parse().unwrap();
+    let rules = rules();
+    c.bench_function(&format!("{}/with_expr", name), |b| {
+        b.iter_batched(
+            || simplify_runner::<L, N>(timeout),
+            |runner| runner.with_expr(black_box(&expr)),
+            BatchSize::SmallInput,
+        )
+    });
+    c.bench_function(&format!("{}/run", name), |b| {
+        b.iter_batched(
+            || simplify_runner(timeout).with_expr(&expr),
+            |runner| runner.run(black_box(&rules)),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let runner = simplify_runner(timeout).with_expr(&expr).run(&rules);
+    c.bench_function(&format!("{}/find_best", name), |b| {
+        b.iter(|| {
+            let extractor = Extractor::new(black_box(&runner.egraph), AstSize);
+            extractor.find_best(runner.roots[0])
+        })
+    });
+}
+
+pub fn phases_benchmark(c: &mut Criterion) {
+    let simpl1 = inputs::simpl1();
+    let simpl2 = inputs::simpl2();
+
+    bench_phases(c, "phases/basic_maths/simpl1", &simpl1, symbollang::basic_maths::basic_maths_rules, 8);
+    bench_phases(c, "phases/basic_maths/simpl2", &simpl2, symbollang::basic_maths::basic_maths_rules, 8);
+    bench_phases(c, "phases/while_superinterpreter/while_10", WHILE_10, symbollang::while_superinterpreter::while_rules, 100);
+
+    bench_phases(c, "customlang/phases/basic_maths/simpl1", &simpl1, customlang::basic_maths::basic_maths_rules, 8);
+    bench_phases(c, "customlang/phases/basic_maths/simpl2", &simpl2, customlang::basic_maths::basic_maths_rules, 8);
+    bench_phases(c, "customlang/phases/while_superinterpreter/while_10", WHILE_10, customlang::while_superinterpreter::while_rules, 100);
+}
+
+criterion_group!(benches, phases_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use criterion::Criterion;
+use egg_benchmark::EGraphSize;
+use log::{warn};
+use std::fmt::Display;
+
+// Every benchmark logs the e-graph size of its last iteration, and the
+// optimisations the cost of their input and result, in the format that
+// scripts/load_results.jl reads.
+
+/// Benchmarks `run`, which returns the size of the e-graph it built. Returns
+/// the size of the last iteration, or `None` if the benchmark was filtered out.
+pub fn bench_size(c: &mut Criterion, name: &str, mut run: impl FnMut() -> EGraphSize) -> Option<EGraphSize> {
+    let mut last = None;
+    c.bench_function(name, |b| {
+        let mut size = EGraphSize{num_classes:0, num_nodes:0, num_memo:0};
+        b.iter(|| {
+            size = run();
+        });
+        warn!("{} {}", name, size);
+        last = Some(size);
+    });
+    last
+}
+
+/// Like `bench_size`, for a run that also returns the cost before and after
+/// optimising.
+pub fn bench_cost<C: Display>(
+    c: &mut Criterion,
+    name: &str,
+    mut run: impl FnMut() -> ((C, C), EGraphSize),
+) -> Option<EGraphSize> {
+    let mut last = None;
+    c.bench_function(name, |b| {
+        let mut size = EGraphSize{num_classes:0, num_nodes:0, num_memo:0};
+        let mut cost = None;
+        b.iter(|| {
+            let (itercost, itersize) = run();
+            size = itersize;
+            cost = Some(itercost);
+        });
+        if let Some((before, after)) = cost {
+            warn!("{} cost: {} -> {}", name, before, after);
+        }
+        warn!("{} {}", name, size);
+        last = Some(size);
+    });
+    last
+}
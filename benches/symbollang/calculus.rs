@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::equivalent;
+use egg_benchmark::inputs::CALCULUS;
+use egg_benchmark::symbollang::calculus::*;
+use crate::report::bench_size;
+
+pub fn calculus_benchmark(c: &mut Criterion) {
+    let rules = calculus_rules();
+
+    // the derivative is usually not the smallest term of its class, so we
+    // check equivalence instead of extracting
+    for (name, expr, goal) in CALCULUS {
+        let expr: RecExpr<SymbolLang> = expr.parse().unwrap();
+        let goal: Pattern<SymbolLang> = goal.parse().unwrap();
+        bench_size(c, &format!("calculus/{}", name), || {
+            let (equal, size) = equivalent(black_box(&expr), black_box(&goal), black_box(&rules), 30);
+            assert!(equal, "{} not proved equal to {}", expr, goal);
+            size
+        });
+    }
+}
+
+criterion_group!(benches, calculus_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::{inputs, optimize};
+use egg_benchmark::circuits::*;
+use egg_benchmark::symbollang::circuits::*;
+use crate::report::bench_cost;
+use log::{warn};
+
+pub fn circuits_benchmark(c: &mut Criterion) {
+    let rules = circuit_rules();
+
+    for (name, src) in inputs::circuits() {
+        let expr: RecExpr<SymbolLang> = src.parse().unwrap();
+        // the quality of the result, as area before and after, and the gate
+        // count of the circuit with the least area
+        let gates = |e: &RecExpr<SymbolLang>| GateCost::gate_count().cost_rec(e);
+        let mut gate_count = (0, 0);
+        let size = bench_cost(c, &format!("circuits/{}", name), || {
+            let (best, cost, size) = optimize(black_box(&expr), black_box(&rules), 10, |_| GateCost::area());
+            gate_count = (gates(&expr), gates(&best));
+            (cost, size)
+        });
+        if size.is_some() {
+            warn!("circuits/{} gates: {} -> {}", name, gate_count.0, gate_count.1);
+        }
+    }
+}
+
+criterion_group!(benches, circuits_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::equivalent;
+use egg_benchmark::inputs::LAMBDA;
+use egg_benchmark::symbollang::lambda::*;
+use crate::report::bench_size;
+
+pub fn lambda_benchmark(c: &mut Criterion) {
+    let rules = lambda_rules();
+
+    for (name, expr, goal) in LAMBDA {
+        let expr: RecExpr<SymbolLang> = expr.parse().unwrap();
+        let goal: Pattern<SymbolLang> = goal.parse().unwrap();
+        bench_size(c, &format!("lambda/{}", name), || {
+            let (equal, size) = equivalent(black_box(&expr), black_box(&goal), black_box(&rules), 30);
+            assert!(equal, "not reduced to {}", goal);
+            size
+        });
+    }
+}
+
+criterion_group!(benches, lambda_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,22 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::{inputs, optimize};
+use egg_benchmark::matrix::*;
+use egg_benchmark::symbollang::matrix::*;
+use crate::report::bench_cost;
+
+pub fn matrix_benchmark(c: &mut Criterion) {
+    let rules = matrix_rules();
+
+    for (name, src) in inputs::matrix() {
+        let expr: RecExpr<SymbolLang> = src.parse().unwrap();
+        // the quality of the result, as FLOPs before and after
+        bench_cost(c, &format!("matrix/{}", name), || {
+            let (_, cost, size) = optimize(black_box(&expr), black_box(&rules), 30, Flops::new);
+            (cost, size)
+        });
+    }
+}
+
+criterion_group!(benches, matrix_benchmark);
+criterion_main!(benches);
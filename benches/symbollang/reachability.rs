@@ -0,0 +1,30 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg_benchmark::inputs;
+use egg_benchmark::reachability::*;
+use egg_benchmark::symbollang::reachability::*;
+use crate::report::bench_size;
+use log::{warn};
+
+pub fn reachability_benchmark(c: &mut Criterion) {
+    // multi-patterns, and the same derivations with the join in the applier
+    let theories = [
+        ("reachability", reachability_rules()),
+        ("reachability_join", reachability_join_rules()),
+    ];
+    for (theory, rules) in &theories {
+        for (name, facts) in inputs::reachability() {
+            let mut paths = 0;
+            let size = bench_size(c, &format!("{}/{}", theory, name), || {
+                let (iterpaths, size) = derive(black_box(&facts), black_box(rules), 100);
+                paths = iterpaths;
+                size
+            });
+            if size.is_some() {
+                warn!("{}/{} paths: {}", theory, name, paths);
+            }
+        }
+    }
+}
+
+criterion_group!(benches, reachability_benchmark);
+criterion_main!(benches);
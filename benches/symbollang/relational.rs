@@ -0,0 +1,22 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::{inputs, optimize};
+use egg_benchmark::relational::*;
+use egg_benchmark::symbollang::relational::*;
+use crate::report::bench_cost;
+
+pub fn relational_benchmark(c: &mut Criterion) {
+    let rules = relational_rules();
+
+    for (name, src) in inputs::relational() {
+        let expr: RecExpr<SymbolLang> = src.parse().unwrap();
+        // the quality of the result, as estimated work before and after
+        bench_cost(c, &format!("relational/{}", name), || {
+            let (_, (before, after), size) = optimize(black_box(&expr), black_box(&rules), 10, Work::new);
+            ((before.round(), after.round()), size)
+        });
+    }
+}
+
+criterion_group!(benches, relational_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::simplify;
+use egg_benchmark::inputs;
+use egg_benchmark::symbollang::basic_maths::*;
+use egg_benchmark::symbollang::variadic_maths::*;
+use egg_benchmark::variadic::flatten;
+use crate::report::bench_size;
+use log::{warn};
+
+pub fn variadic_maths_benchmark(c: &mut Criterion) {
+    let rules = variadic_maths_rules();
+    let binary_rules = basic_maths_rules();
+
+    // the basic_maths inputs, with n-ary + and * as Julia parses them
+    for (name, src) in inputs::maths() {
+        let expr: RecExpr<SymbolLang> = flatten(&src.parse().unwrap());
+        // the same input in the binary encoding of basic_maths
+        let binary: RecExpr<SymbolLang> = src.parse().unwrap();
+        let (_, binary_size) = simplify(&binary, &binary_rules, 8);
+        let size = bench_size(c, &format!("variadic_maths/{}", name), || {
+            simplify(black_box(&expr), black_box(&rules), 8).1
+        });
+        if let Some(size) = size {
+            warn!(
+                "variadic_maths/{} vs binary: classes {} vs {}, nodes {} vs {}",
+                name, size.num_classes, binary_size.num_classes, size.num_nodes, binary_size.num_nodes
+            );
+        }
+    }
+}
+
+criterion_group!(benches, variadic_maths_benchmark);
+criterion_main!(benches);
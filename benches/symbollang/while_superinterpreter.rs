@@ -0,0 +1,20 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::{simplify, to_julia};
+use egg_benchmark::inputs::WHILE_10;
+use egg_benchmark::symbollang::while_superinterpreter::*;
+use crate::report::bench_size;
+
+pub fn while_superinterpreter_benchmark(c: &mut Criterion) {
+    let rules = while_rules();
+
+    let expr: RecExpr<SymbolLang> = WHILE_10.parse().unwrap();
+    bench_size(c, "while_superinterpreter/while_10", || {
+        let (result, size) = simplify(black_box(&expr), black_box(&rules), 100);
+        assert_eq!(result, "10".parse().unwrap(), "interpreted to {}", to_julia(&result));
+        size
+    });
+}
+
+criterion_group!(benches, while_superinterpreter_benchmark);
+criterion_main!(benches);
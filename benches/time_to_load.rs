@@ -0,0 +1,76 @@
+// Metatheory.jl reports `time_to_load`, the time to load the package and its
+// theories. The closest equivalent in egg is the one-time cost of setting up a
+// theory: parsing all patterns into the `Rewrite` vector, creating the
+// `Runner` and adding the first expression to its `EGraph`. These costs are
+// included in the other benchmarks but measured here without saturation.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egg::*;
+use egg_benchmark::inputs::{self, WHILE_10};
+use egg_benchmark::{customlang, symbollang};
+
+fn load<L, N>(rules: fn() -> Vec<Rewrite<L, N>>, expr: &str) -> (Vec<Rewrite<L, N>>, Runner<L, N>)
+where
+    L: Language + FromOp,
+    N: Analysis<L> + Default,
+{
+    let rules = rules();
+    let expr: RecExpr<L> = expr.parse().unwrap();
+    let runner = Runner::default().with_expr(&expr);
+    (rules, runner)
+}
+
+pub fn time_to_load_benchmark(c: &mut Criterion) {
+    let simpl1 = inputs::simpl1();
+    let demorgan = inputs::demorgan();
+    let prove1 = inputs::prove1();
+
+    c.bench_function("time_to_load/basic_maths", |b| {
+        b.iter_with_large_drop(|| load(symbollang::basic_maths::basic_maths_rules, black_box(&simpl1)))
+    });
+    c.bench_function("time_to_load/calc_logic", |b| {
+        b.iter_with_large_drop(|| load(symbollang::calc_logic::calculational_logic_rules, black_box(&demorgan)))
+    });
+    c.bench_function("time_to_load/prop_logic", |b| {
+        b.iter_with_large_drop(|| load(symbollang::prop_logic::propositional_logic_rules, black_box(&prove1)))
+    });
+    c.bench_function("time_to_load/while_superinterpreter", |b| {
+        b.iter_with_large_drop(|| load(symbollang::while_superinterpreter::while_rules, black_box(WHILE_10)))
+    });
+    c.bench_function("time_to_load", |b| {
+        b.iter_with_large_drop(|| {
+            (
+                load(symbollang::basic_maths::basic_maths_rules, black_box(&simpl1)),
+                load(symbollang::calc_logic::calculational_logic_rules, black_box(&demorgan)),
+                load(symbollang::prop_logic::propositional_logic_rules, black_box(&prove1)),
+                load(symbollang::while_superinterpreter::while_rules, black_box(WHILE_10)),
+            )
+        })
+    });
+
+    c.bench_function("customlang/time_to_load/basic_maths", |b| {
+        b.iter_with_large_drop(|| load(customlang::basic_maths::basic_maths_rules, black_box(&simpl1)))
+    });
+    c.bench_function("customlang/time_to_load/calc_logic", |b| {
+        b.iter_with_large_drop(|| load(customlang::calc_logic::calculational_logic_rules, black_box(&demorgan)))
+    });
+    c.bench_function("customlang/time_to_load/prop_logic", |b| {
+        b.iter_with_large_drop(|| load(customlang::prop_logic::propositional_logic_rules, black_box(&prove1)))
+    });
+    c.bench_function("customlang/time_to_load/while_superinterpreter", |b| {
+        b.iter_with_large_drop(|| load(customlang::while_superinterpreter::while_rules, black_box(WHILE_10)))
+    });
+    c.bench_function("customlang/time_to_load", |b| {
+        b.iter_with_large_drop(|| {
+            (
+                load(customlang::basic_maths::basic_maths_rules, black_box(&simpl1)),
+                load(customlang::calc_logic::calculational_logic_rules, black_box(&demorgan)),
+                load(customlang::prop_logic::propositional_logic_rules, black_box(&prove1)),
+                load(customlang::while_superinterpreter::while_rules, black_box(WHILE_10)),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, time_to_load_benchmark);
+criterion_main!(benches);
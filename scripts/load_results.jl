@@ -61,9 +61,18 @@ function load_results(path::String)
     
     # parse egraph size from logs
     pat = r"\[[^\]]+] (?<bench>.+) n_classes: (?<n_classes>\d+), n_nodes: (?<n_nodes>\d+), n_memo: (?<n_memo>\d+)"
+    # cost of the input and of the extracted term, logged by optimisation benchmarks
+    cost_pat = r"\[[^\]]+] (?<bench>.+) cost: (?<before>\d+) -> (?<after>\d+)"
     open("./target/egg-log.txt", "r") do io
         d = Dict()
         for line in eachline(io)
+            c = match(cost_pat, line)
+            if !isnothing(c)
+                bench = replace(c["bench"], "/" => "_")
+                push!(od[bench], "cost_before" => parse(Int, c["before"]))
+                push!(od[bench], "cost" => parse(Int, c["after"]))
+                continue
+            end
             m = match(pat, line)
             !isnothing(m) || continue
             bench = replace(m["bench"], "/" => "_")
@@ -146,6 +155,15 @@ function format_size(val::Dict)
 end
 format_size(::Missing; kw...) = @sprintf("")
 
+function format_cost(val::Dict)
+    if haskey(val, "cost")
+        @sprintf("%i -> %i", val["cost_before"], val["cost"])
+    else
+        ""
+    end
+end
+format_cost(::Missing; kw...) = @sprintf("")
+
 function ratio_column!(combined_results, c1, c2, ratiokeys...)
     all_keys = combined_results[c1] |> keys
     col = OrderedDict{String,Dict}()
@@ -226,3 +244,18 @@ if !isnothing(OUTPUT)
     end
 end
 println(table)
+
+# append a table with the cost before and after optimisation, for the
+# benchmarks that extract with a custom cost function
+table = AirspeedVelocity.create_table(
+    new_res,
+    formatter=v->format_cost(v)
+)
+
+if !isnothing(OUTPUT)
+    open(OUTPUT, "a") do io
+        println(io)
+        write(io, table)
+    end
+end
+println(table)
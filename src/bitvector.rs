@@ -0,0 +1,158 @@
+// Shared pieces of the bitvector theory: 8-bit words with wrapping `+`, `-`
+// and `*`, shifts `<<` and `>>` (shifting by 8 or more gives 0), `&`, `|`, `^`
+// and `~`. Constants are folded by an analysis with the same wrap-around
+// semantics as the evaluator, which also checks the rules on random inputs.
+
+use crate::eval::num_vars;
+use egg::*;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+pub type Word = u8;
+
+/// Folds an operator over the constant values of its children, if they are
+/// known.
+pub fn fold(op: &str, args: &[Option<Word>]) -> Option<Word> {
+    let shift = |b: Word| u32::from(b);
+    match (op, args) {
+        ("+", [Some(a), Some(b)]) => Some(a.wrapping_add(*b)),
+        ("-", [Some(a), Some(b)]) => Some(a.wrapping_sub(*b)),
+        ("*", [Some(a), Some(b)]) => Some(a.wrapping_mul(*b)),
+        ("<<", [Some(a), Some(b)]) => Some(a.checked_shl(shift(*b)).unwrap_or(0)),
+        (">>", [Some(a), Some(b)]) => Some(a.checked_shr(shift(*b)).unwrap_or(0)),
+        ("&", [Some(a), Some(b)]) => Some(a & b),
+        ("|", [Some(a), Some(b)]) => Some(a | b),
+        ("^", [Some(a), Some(b)]) => Some(a ^ b),
+        ("~", [Some(a)]) => Some(!a),
+        _ => None,
+    }
+}
+
+pub fn merge_constant(to: &mut Option<Word>, from: Option<Word>) -> DidMerge {
+    merge_option(to, from, |a, b| {
+        debug_assert_eq!(*a, b, "merged e-classes with different constants");
+        DidMerge(false, false)
+    })
+}
+
+/// Adds the constant to the class of `id`, so that it can be extracted.
+pub fn add_constant<L, N>(egraph: &mut EGraph<L, N>, id: Id)
+where
+    L: Language + FromOp,
+    N: Analysis<L, Data = Option<Word>>,
+{
+    if let Some(c) = egraph[id].data {
+        let added = egraph.add(L::from_op(&c.to_string(), vec![]).unwrap());
+        egraph.union(id, added);
+    }
+}
+
+fn eval_word_at<L: Language + Display>(expr: &RecExpr<L>, id: Id, env: &HashMap<String, Word>) -> Option<Word> {
+    let node = &expr[id];
+    let name = node.to_string();
+    if node.is_leaf() {
+        return name.parse().ok().or_else(|| env.get(&name).copied());
+    }
+    let args: Vec<Option<Word>> = node.children().iter().map(|c| eval_word_at(expr, *c, env)).collect();
+    fold(&name, &args)
+}
+
+/// Evaluates a bitvector term or pattern under `env`. Returns `None` for
+/// unknown operators, unassigned variables and constants that are not words.
+pub fn eval_word<L: Language + Display>(expr: &RecExpr<L>, env: &HashMap<String, Word>) -> Option<Word> {
+    eval_word_at(expr, Id::from(expr.as_ref().len() - 1), env)
+}
+
+/// A random word that is one of the edge cases of wrap-around half of the
+/// time.
+pub fn random_word(rng: &mut impl Rng) -> Word {
+    const EDGES: [Word; 6] = [0, 1, 2, 0x7f, 0x80, 0xff];
+    if rng.gen() {
+        EDGES[rng.gen_range(0..EDGES.len())]
+    } else {
+        rng.gen()
+    }
+}
+
+/// Evaluates both sides of `rule` under `trials` random assignments of its
+/// pattern variables, and returns one under which they differ. Rules whose
+/// sides are not patterns are not checked.
+pub fn find_counterexample<L, N>(rule: &Rewrite<L, N>, trials: usize, rng: &mut impl Rng) -> Option<HashMap<String, Word>>
+where
+    L: Language + Display,
+    N: Analysis<L>,
+{
+    let lhs = rule.searcher.get_pattern_ast()?;
+    let rhs = rule.applier.get_pattern_ast()?;
+    let vars = num_vars(lhs);
+    (0..trials)
+        .map(|_| vars.iter().map(|v| (v.clone(), random_word(rng))).collect())
+        .find(|env| eval_word(lhs, env) != eval_word(rhs, env))
+}
+
+/// Strength reductions of multiplications by `2^k` to shifts, which are written
+/// out per `k` because a rule cannot compute the shift amount.
+pub fn mul_pow2_rules<L, N>() -> Vec<Rewrite<L, N>>
+where
+    L: Language + FromOp + Display + Send + Sync + 'static,
+    N: Analysis<L>,
+{
+    (1..8)
+        .map(|k| {
+            let lhs: Pattern<L> = format!("(* ?a {})", 1 << k).parse().unwrap();
+            let rhs: Pattern<L> = format!("(<< ?a {})", k).parse().unwrap();
+            Rewrite::new(format!("mul-pow2-{}", k), lhs, rhs).unwrap()
+        })
+        .collect()
+}
+
+/// Instruction latencies, as on a typical in-order core: a multiplication
+/// takes three cycles and every other operation one. Leaves are free.
+#[derive(Debug, Clone, Copy)]
+pub struct Latency;
+
+impl<L: Language + Display> CostFunction<L> for Latency {
+    type Cost = usize;
+
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        let latency = match (enode.to_string().as_str(), enode.len()) {
+            (_, 0) => 0,
+            ("*", 2) => 3,
+            _ => 1,
+        };
+        enode.fold(latency, |sum, id| sum + costs(id))
+    }
+}
+
+/// `x0 + ... + x{n-1}`, where every addition is obfuscated by one of the
+/// mixed boolean-arithmetic identities `a + b = (a ^ b) + ((a & b) << 1)` and
+/// `a + b = (a | b) + (a & b)`. The term doubles in size with every summand.
+pub fn mba_sum(n: usize) -> String {
+    assert!(n > 0);
+    (1..n).fold("x0".to_string(), |sum, i| {
+        if i % 2 == 1 {
+            format!("(+ (^ {s} x{i}) (<< (& {s} x{i}) 1))", s = sum, i = i)
+        } else {
+            format!("(+ (| {s} x{i}) (& {s} x{i}))", s = sum, i = i)
+        }
+    })
+}
+
+/// `x0 * 1 + x1 * 2 + ... + x{n-1} * 2^(n-1)`, with the powers wrapping
+/// around at 2^8, so that all multiplications reduce to shifts or vanish.
+pub fn shift_sum(n: usize) -> String {
+    assert!(n > 0);
+    let term = |i: usize| format!("(* x{} {})", i, (1u64 << i) & 0xff);
+    (1..n).fold(term(0), |sum, i| format!("(+ {} {})", sum, term(i)))
+}
+
+/// `x0 + (200 + (x1 + (200 + ...)))` with `n` variables, where the constants
+/// only fold, with wrap-around, after reassociation.
+pub fn constant_chain(n: usize) -> String {
+    assert!(n > 0);
+    (0..n).rev().fold("0".to_string(), |rest, i| format!("(+ x{} (+ 200 {}))", i, rest))
+}
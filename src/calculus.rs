@@ -0,0 +1,81 @@
+// Shared pieces of the symbolic differentiation theory, modelled after the
+// `math` test suite of egg. Constants are exact rationals and are folded by an
+// analysis; the conditions below read it.
+
+use crate::eval::Rational;
+use egg::*;
+use std::fmt::Display;
+
+/// Folds an operator over the constant values of its children, if they are
+/// known. `ln`, `sin` and `cos` are never folded.
+pub fn fold(op: &str, args: &[Option<Rational>]) -> Option<Rational> {
+    match (op, args) {
+        ("+", [Some(a), Some(b)]) => a.checked_add(*b),
+        ("-", [Some(a), Some(b)]) => a.checked_add(-*b),
+        ("*", [Some(a), Some(b)]) => a.checked_mul(*b),
+        ("/", [Some(a), Some(b)]) => a.checked_mul(b.checked_inv()?),
+        ("pow", [Some(a), Some(b)]) => a.checked_pow(*b),
+        _ => None,
+    }
+}
+
+pub fn merge_constant(to: &mut Option<Rational>, from: Option<Rational>) -> DidMerge {
+    merge_option(to, from, |a, b| {
+        debug_assert_eq!(*a, b, "merged e-classes with different constants");
+        DidMerge(false, false)
+    })
+}
+
+/// Adds the constant to the class of `id`, so that it can be extracted.
+pub fn add_constant<L, N>(egraph: &mut EGraph<L, N>, id: Id)
+where
+    L: Language + FromOp,
+    N: Analysis<L, Data = Option<Rational>>,
+{
+    if let Some(c) = egraph[id].data {
+        let added = egraph.add(L::from_op(&c.to_string(), vec![]).unwrap());
+        egraph.union(id, added);
+    }
+}
+
+/// Whether the class is not known to be zero. As in egg's math suite, classes
+/// without a constant count as non-zero.
+pub fn is_not_zero<L, N>(var: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L, Data = Option<Rational>>,
+{
+    let var: Var = var.parse().unwrap();
+    move |egraph, _, subst| egraph[subst[var]].data != Some(Rational::int(0))
+}
+
+fn has_symbol<L, N>(egraph: &EGraph<L, N>, id: Id) -> bool
+where
+    L: Language + Display,
+    N: Analysis<L, Data = Option<Rational>>,
+{
+    egraph[id].nodes.iter().any(|n| n.is_leaf() && n.to_string().parse::<Rational>().is_err())
+}
+
+pub fn is_sym<L, N>(var: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language + Display,
+    N: Analysis<L, Data = Option<Rational>>,
+{
+    let var: Var = var.parse().unwrap();
+    move |egraph, _, subst| has_symbol(egraph, subst[var])
+}
+
+/// Whether `v` is a constant or a variable other than `w`.
+pub fn is_const_or_distinct_var<L, N>(v: &str, w: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language + Display,
+    N: Analysis<L, Data = Option<Rational>>,
+{
+    let v: Var = v.parse().unwrap();
+    let w: Var = w.parse().unwrap();
+    move |egraph, _, subst| {
+        egraph.find(subst[v]) != egraph.find(subst[w])
+            && (egraph[subst[v]].data.is_some() || has_symbol(egraph, subst[v]))
+    }
+}
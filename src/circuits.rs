@@ -0,0 +1,104 @@
+// Shared pieces of the boolean circuit theory: a cost function counting gates
+// or their area, and generators for small unoptimised circuits. Circuits are
+// written with the gates `and`, `or`, `xor`, `not` and `mux`; the generators
+// spell `xor` and majority out as sums of products so that the rules have
+// something to optimise.
+
+use egg::*;
+use std::fmt::Display;
+
+/// The cost of every gate kind. Inputs and constants are free. Costs are
+/// summed over the extracted tree, so a shared subcircuit is paid for at every
+/// use.
+#[derive(Debug, Clone, Copy)]
+pub struct GateCost {
+    pub not: usize,
+    pub and: usize,
+    pub or: usize,
+    pub xor: usize,
+    pub mux: usize,
+}
+
+impl GateCost {
+    /// Every gate costs one.
+    pub fn gate_count() -> Self {
+        GateCost { not: 1, and: 1, or: 1, xor: 1, mux: 1 }
+    }
+
+    /// Transistor counts of static CMOS gates.
+    pub fn area() -> Self {
+        GateCost { not: 2, and: 6, or: 6, xor: 12, mux: 12 }
+    }
+
+    pub fn gate<L: Language + Display>(&self, enode: &L) -> usize {
+        match (enode.to_string().as_str(), enode.len()) {
+            ("not", 1) => self.not,
+            ("and", 2) => self.and,
+            ("or", 2) => self.or,
+            ("xor", 2) => self.xor,
+            ("mux", 3) => self.mux,
+            _ => 0,
+        }
+    }
+}
+
+impl<L: Language + Display> CostFunction<L> for GateCost {
+    type Cost = usize;
+
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        enode.fold(self.gate(enode), |sum, id| sum + costs(id))
+    }
+}
+
+fn xor_sop(x: &str, y: &str) -> String {
+    format!("(or (and {x} (not {y})) (and (not {x}) {y}))", x = x, y = y)
+}
+
+fn majority_sop(x: &str, y: &str, z: &str) -> String {
+    format!("(or (or (and {x} {y}) (and {x} {z})) (and {y} {z}))", x = x, y = y, z = z)
+}
+
+/// The most significant sum bit of an `n`-bit ripple-carry adder of `a` and
+/// `b` with carry-in `cin`, which depends on the whole carry chain.
+pub fn adder(n: usize) -> String {
+    assert!(n > 0);
+    let mut carry = "cin".to_string();
+    for i in 0..n - 1 {
+        carry = majority_sop(&format!("a{}", i), &format!("b{}", i), &carry);
+    }
+    let top = xor_sop(&format!("a{}", n - 1), &format!("b{}", n - 1));
+    xor_sop(&top, &carry)
+}
+
+/// `a > b` for `n`-bit unsigned numbers, as a sum of products over the bits.
+pub fn comparator(n: usize) -> String {
+    assert!(n > 0);
+    let equal = |j: usize| format!("(or (and a{j} b{j}) (and (not a{j}) (not b{j})))", j = j);
+    let products: Vec<String> = (0..n)
+        .map(|i| {
+            (i + 1..n).fold(format!("(and a{i} (not b{i}))", i = i), |p, j| format!("(and {} {})", p, equal(j)))
+        })
+        .collect();
+    products.into_iter().reduce(|sum, p| format!("(or {} {})", sum, p)).unwrap()
+}
+
+/// The parity of the inputs `x0` to `x{n-1}`, as a balanced tree of `xor`s
+/// written as sums of products.
+pub fn parity(n: usize) -> String {
+    assert!(n > 0);
+    let mut layer: Vec<String> = (0..n).map(|i| format!("x{}", i)).collect();
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| match pair {
+                [x, y] => xor_sop(x, y),
+                [x] => x.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    layer.pop().unwrap()
+}
@@ -0,0 +1,98 @@
+use crate::nonzero::*;
+use crate::sorts::*;
+use egg::*;
+
+define_language! {
+    pub enum BasicMath {
+        Num(i32),
+        "+" = Add([Id; 2]),
+        "-" = Sub([Id; 2]),
+        "-" = Neg([Id; 1]),
+        "*" = Mul([Id; 2]),
+        "^" = Pow([Id; 2]),
+        "inv" = Inv([Id; 1]),
+        Symbol(Symbol),
+    }
+}
+
+// BasicMath with division
+define_language! {
+    pub enum DivisionMath {
+        Num(i32),
+        "+" = Add([Id; 2]),
+        "-" = Sub([Id; 2]),
+        "-" = Neg([Id; 1]),
+        "*" = Mul([Id; 2]),
+        "/" = Div([Id; 2]),
+        "^" = Pow([Id; 2]),
+        "inv" = Inv([Id; 1]),
+        Symbol(Symbol),
+    }
+}
+
+pub fn basic_maths_rules() -> Vec<Rewrite<BasicMath, ()>> {
+    vec![
+        // monoid +
+        rewrite!("commute-add"; "(+ ?a ?b)" => "(+ ?b ?a)"),
+        rewrite!("assoc-add-1"; "(+ (+ ?a ?b) ?c)" => "(+ ?a (+ ?b ?c))"),
+        rewrite!("assoc-add-2"; "(+ ?a (+ ?b ?c))" => "(+ (+ ?a ?b) ?c)"),
+        rewrite!("add-0"; "(+ ?a 0)" => "?a"),
+
+        // monoid *
+        rewrite!("commute-mul"; "(* ?a ?b)" => "(* ?b ?a)"),
+        rewrite!("assoc-mul-1"; "(* (* ?a ?b) ?c)" => "(* ?a (* ?b ?c))"),
+        rewrite!("assoc-mul-2"; "(* ?a (* ?b ?c))" => "(* (* ?a ?b) ?c)"),
+        rewrite!("mul-a0"; "(* ?a 0)" => "0"),
+        rewrite!("mul-0a"; "(* 0 ?a)" => "0"),
+        rewrite!("mul-a1"; "(* ?a 1)" => "?a"),
+        rewrite!("mul-1a"; "(* 1 ?a)" => "?a"),
+
+        // minus
+        rewrite!("minus-0"; "(- ?a ?a)" => "0"),
+        rewrite!("minus-1"; "(+ ?a (- ?b))" => "(- ?a ?b)"),
+
+        // distributive
+        rewrite!("distr-abc"; "(* ?a (+ ?b ?c))" => "(+ (* ?a ?b) (* ?a ?c))"),
+        rewrite!("distr-aba"; "(+ ?a (* ?b ?a))" => "(* (+ ?b 1)  ?a)"),
+
+        // powers
+        //(y^n) * y --> y^(n + 1)
+        rewrite!("power-yny"; "(* (^ ?y ?n) ?y)" => "(^ ?y (+ ?n 1))"),
+        //x^n * x^m == x^(n + m)
+        rewrite!("power-ynym-1"; "(* (^ ?y ?n) (^ ?y ?m))" => "(^ ?y (+ ?n ?m))"),
+        rewrite!("power-ynym-2"; "(^ ?y (+ ?n ?m))" => "(* (^ ?y ?n) (^ ?y ?m))"),
+        //(x * y)^z == x^z * y^z
+        rewrite!("power-distr-1"; "(^ (* ?x ?y) ?z)" => "(* (^ ?x ?z) (^ ?y ?z))"),
+        rewrite!("power-distr-2"; "(* (^ ?x ?z) (^ ?y ?z))" => "(^ (* ?x ?y) ?z)"),
+        //(x^p)^q == x^(p * q)
+        rewrite!("power-power-1"; "(^ (^ ?x ?p) ?q)" => "(^ ?x (* ?p ?q))"),
+        rewrite!("power-power-2"; "(^ ?x (* ?p ?q))" => "(^ (^ ?x ?p) ?q)"),
+        //x^0 --> 1
+        rewrite!("power-x0"; "(^ ?x 0)" => "1"),
+        //0^x --> 0
+        rewrite!("power-0x"; "(^ 0 ?x)" => "0"),
+        //1^x --> 1
+        rewrite!("power-1x"; "(^ 1 ?x)" => "1"),
+        //x^1 --> x
+        rewrite!("power-x1"; "(^ ?x 1)" => "?x"),
+        //inv(x) == x^(-1)
+        rewrite!("power-inv"; "(inv ?x)" => "(^ ?x (- 1))")
+    ]
+}
+
+// basic_maths_rules restricted to operands that may be integers, so that e.g.
+// (+ true 0) is not simplified to true
+pub fn basic_maths_sorted_rules() -> Vec<Rewrite<BasicMath, Sorts>> {
+    restrict(&basic_maths_rules(), Sort::Int)
+}
+
+// basic_maths_rules with division, where the rules that divide by an operand
+// only apply if it is known to be non-zero
+pub fn basic_maths_division_rules() -> Vec<Rewrite<DivisionMath, NonZero>> {
+    [with_analysis(&basic_maths_rules(), &REPLACED_BY_DIVISION_RULES), division_rules(true)].concat()
+}
+
+// the same rules without the non-zero checks, which is unsound
+pub fn basic_maths_unchecked_division_rules() -> Vec<Rewrite<DivisionMath, NonZero>> {
+    [with_analysis(&basic_maths_rules(), &REPLACED_BY_DIVISION_RULES), division_rules(false)].concat()
+}
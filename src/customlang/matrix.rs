@@ -0,0 +1,61 @@
+use crate::matrix::*;
+use egg::*;
+
+define_language! {
+    pub enum Matrix {
+        Num(usize),
+        "mat" = Mat([Id; 3]),
+        "*" = Mul([Id; 2]),
+        "+" = Add([Id; 2]),
+        "transpose" = Transpose(Id),
+        "scale" = Scale([Id; 2]),
+        Symbol(Symbol),
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Dimensions;
+
+impl Analysis<Matrix> for Dimensions {
+    type Data = Option<Shape>;
+
+    fn make(egraph: &EGraph<Matrix, Self>, enode: &Matrix) -> Self::Data {
+        let x = |i: &Id| egraph[*i].data;
+        match enode {
+            Matrix::Num(n) => Some(Shape::Size(*n)),
+            Matrix::Mat([a, r, c]) => shape("mat", &[x(a), x(r), x(c)]),
+            Matrix::Mul([a, b]) => shape("*", &[x(a), x(b)]),
+            Matrix::Add([a, b]) => shape("+", &[x(a), x(b)]),
+            Matrix::Transpose(a) => shape("transpose", &[x(a)]),
+            Matrix::Scale([s, a]) => shape("scale", &[x(s), x(a)]),
+            Matrix::Symbol(_) => None,
+        }
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_shape(to, from)
+    }
+}
+
+pub fn matrix_rules() -> Vec<Rewrite<Matrix, Dimensions>> {
+    [
+        // products and sums
+        rewrite!("mul-assoc"; "(* (* ?a ?b) ?c)" <=> "(* ?a (* ?b ?c))"),
+        vec![rewrite!("add-comm"; "(+ ?a ?b)" => "(+ ?b ?a)")],
+        rewrite!("add-assoc"; "(+ (+ ?a ?b) ?c)" <=> "(+ ?a (+ ?b ?c))"),
+        rewrite!("mul-add-distr"; "(* ?a (+ ?b ?c))" <=> "(+ (* ?a ?b) (* ?a ?c))"),
+        rewrite!("add-mul-distr"; "(* (+ ?a ?b) ?c)" <=> "(+ (* ?a ?c) (* ?b ?c))"),
+
+        // transposes
+        vec![rewrite!("transpose-transpose"; "(transpose (transpose ?a))" => "?a")],
+        rewrite!("transpose-mul"; "(transpose (* ?a ?b))" <=> "(* (transpose ?b) (transpose ?a))"),
+        rewrite!("transpose-add"; "(transpose (+ ?a ?b))" <=> "(+ (transpose ?a) (transpose ?b))"),
+        rewrite!("transpose-scale"; "(transpose (scale ?s ?a))" <=> "(scale ?s (transpose ?a))"),
+
+        // scaling
+        rewrite!("scale-mul-left"; "(* (scale ?s ?a) ?b)" <=> "(scale ?s (* ?a ?b))"),
+        rewrite!("scale-mul-right"; "(* ?a (scale ?s ?b))" <=> "(scale ?s (* ?a ?b))"),
+        rewrite!("scale-add"; "(scale ?s (+ ?a ?b))" <=> "(+ (scale ?s ?a) (scale ?s ?b))"),
+    ]
+    .concat()
+}
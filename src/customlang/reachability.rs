@@ -0,0 +1,27 @@
+use crate::reachability::*;
+use egg::*;
+
+define_language! {
+    pub enum Graph {
+        "edge" = Edge([Id; 2]),
+        "path" = Path([Id; 2]),
+        Symbol(Symbol),
+    }
+}
+
+pub fn reachability_rules() -> Vec<Rewrite<Graph, ()>> {
+    vec![
+        // edge(x, y) --> path(x, y)
+        rewrite!("path-base"; "(edge ?x ?y)" => "(path ?x ?y)"),
+        // edge(x, y), path(y, z) --> path(x, z)
+        multi_rewrite!("path-trans"; "?t = (edge ?x ?y), ?t = (path ?y ?z)" => "?t = (path ?x ?z)"),
+    ]
+}
+
+// the same rules, with the join of path-trans done by the applier
+pub fn reachability_join_rules() -> Vec<Rewrite<Graph, ()>> {
+    vec![
+        rewrite!("path-base"; "(edge ?x ?y)" => "(path ?x ?y)"),
+        rewrite!("path-trans"; "(edge ?x ?y)" => { JoinPaths::new("?x", "?y") }),
+    ]
+}
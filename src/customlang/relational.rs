@@ -0,0 +1,75 @@
+use crate::relational::*;
+use egg::*;
+
+define_language! {
+    pub enum Relation {
+        Num(i64),
+        "table" = Table([Id; 2]),
+        "select" = Select([Id; 2]),
+        "project" = Project([Id; 2]),
+        "join" = Join([Id; 3]),
+        "union" = Union([Id; 2]),
+        "cols" = Cols(Box<[Id]>),
+        "=" = Eq([Id; 2]),
+        "<" = Lt([Id; 2]),
+        "and" = And([Id; 2]),
+        "or" = Or([Id; 2]),
+        "not" = Not(Id),
+        Symbol(Symbol),
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Statistics;
+
+impl Analysis<Relation> for Statistics {
+    type Data = RelData;
+
+    fn make(egraph: &EGraph<Relation, Self>, enode: &Relation) -> Self::Data {
+        match enode {
+            Relation::Num(_) | Relation::Symbol(_) => leaf_data(&enode.to_string()),
+            _ => {
+                let args: Vec<&RelData> = enode.children().iter().map(|i| &egraph[*i].data).collect();
+                make_data(&enode.to_string(), &args)
+            }
+        }
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_data(to, from)
+    }
+}
+
+pub fn relational_rules() -> Vec<Rewrite<Relation, Statistics>> {
+    [
+        // joins
+        vec![rewrite!("join-comm"; "(join ?p ?r ?s)" => "(join ?p ?s ?r)")],
+        rewrite!("join-select"; "(join ?p ?r ?s)" <=> "(select ?p (join true ?r ?s))"),
+        rewrite!("product-assoc"; "(join true (join true ?r ?s) ?t)" <=> "(join true ?r (join true ?s ?t))"),
+
+        // selections
+        rewrite!("select-split"; "(select (and ?p ?q) ?r)" <=> "(select ?p (select ?q ?r))"),
+        vec![rewrite!("select-comm"; "(select ?p (select ?q ?r))" => "(select ?q (select ?p ?r))")],
+        vec![rewrite!("select-true"; "(select true ?r)" => "?r")],
+        vec![rewrite!("select-push-left"; "(select ?p (join ?q ?r ?s))" => "(join ?q (select ?p ?r) ?s)"
+            if refers_only_to("?p", "?r"))],
+        vec![rewrite!("select-push-right"; "(select ?p (join ?q ?r ?s))" => "(join ?q ?r (select ?p ?s))"
+            if refers_only_to("?p", "?s"))],
+        vec![rewrite!("select-project"; "(select ?p (project ?c ?r))" => "(project ?c (select ?p ?r))")],
+        rewrite!("select-union"; "(select ?p (union ?r ?s))" <=> "(union (select ?p ?r) (select ?p ?s))"),
+
+        // projections and unions
+        vec![rewrite!("project-project"; "(project ?c (project ?d ?r))" => "(project ?c ?r)")],
+        rewrite!("project-union"; "(project ?c (union ?r ?s))" <=> "(union (project ?c ?r) (project ?c ?s))"),
+        vec![rewrite!("union-comm"; "(union ?r ?s)" => "(union ?s ?r)")],
+        rewrite!("union-assoc"; "(union (union ?r ?s) ?t)" <=> "(union ?r (union ?s ?t))"),
+
+        // predicates
+        vec![rewrite!("and-comm"; "(and ?p ?q)" => "(and ?q ?p)")],
+        rewrite!("and-assoc"; "(and (and ?p ?q) ?r)" <=> "(and ?p (and ?q ?r))"),
+        vec![rewrite!("or-comm"; "(or ?p ?q)" => "(or ?q ?p)")],
+        vec![rewrite!("eq-comm"; "(= ?a ?b)" => "(= ?b ?a)")],
+        vec![rewrite!("and-true"; "(and ?p true)" => "?p")],
+    ]
+    .concat()
+}
@@ -0,0 +1,67 @@
+use crate::variadic::*;
+use egg::*;
+
+define_language! {
+    pub enum VariadicMath {
+        Num(i32),
+        "+" = Add(Box<[Id]>),
+        "-" = Sub([Id; 2]),
+        "-" = Neg([Id; 1]),
+        "*" = Mul(Box<[Id]>),
+        "^" = Pow([Id; 2]),
+        "inv" = Inv([Id; 1]),
+        // function application `f(x)`, as in the inputs of the egraph benchmarks
+        "call" = Call([Id; 2]),
+        Symbol(Symbol),
+    }
+}
+
+/// The rules of `basic_maths_rules`, with the monoid rules replaced by their
+/// variadic versions. The remaining rules match binary calls, which the
+/// `split-*` rules provide.
+pub fn variadic_maths_rules() -> Vec<Rewrite<VariadicMath, ()>> {
+    [
+        // monoid +
+        ac_rules("add", "+"),
+        unit_rules("add", "+", "0"),
+
+        // monoid *
+        ac_rules("mul", "*"),
+        unit_rules("mul", "*", "1"),
+        absorbing_rules("mul", "*", "0"),
+
+        vec![
+            // minus
+            rewrite!("minus-0"; "(- ?a ?a)" => "0"),
+            rewrite!("minus-1"; "(+ ?a (- ?b))" => "(- ?a ?b)"),
+
+            // distributive
+            rewrite!("distr-abc"; "(* ?a (+ ?b ?c))" => "(+ (* ?a ?b) (* ?a ?c))"),
+            rewrite!("distr-aba"; "(+ ?a (* ?b ?a))" => "(* (+ ?b 1)  ?a)"),
+
+            // powers
+            //(y^n) * y --> y^(n + 1)
+            rewrite!("power-yny"; "(* (^ ?y ?n) ?y)" => "(^ ?y (+ ?n 1))"),
+            //x^n * x^m == x^(n + m)
+            rewrite!("power-ynym-1"; "(* (^ ?y ?n) (^ ?y ?m))" => "(^ ?y (+ ?n ?m))"),
+            rewrite!("power-ynym-2"; "(^ ?y (+ ?n ?m))" => "(* (^ ?y ?n) (^ ?y ?m))"),
+            //(x * y)^z == x^z * y^z
+            rewrite!("power-distr-1"; "(^ (* ?x ?y) ?z)" => "(* (^ ?x ?z) (^ ?y ?z))"),
+            rewrite!("power-distr-2"; "(* (^ ?x ?z) (^ ?y ?z))" => "(^ (* ?x ?y) ?z)"),
+            //(x^p)^q == x^(p * q)
+            rewrite!("power-power-1"; "(^ (^ ?x ?p) ?q)" => "(^ ?x (* ?p ?q))"),
+            rewrite!("power-power-2"; "(^ ?x (* ?p ?q))" => "(^ (^ ?x ?p) ?q)"),
+            //x^0 --> 1
+            rewrite!("power-x0"; "(^ ?x 0)" => "1"),
+            //0^x --> 0
+            rewrite!("power-0x"; "(^ 0 ?x)" => "0"),
+            //1^x --> 1
+            rewrite!("power-1x"; "(^ 1 ?x)" => "1"),
+            //x^1 --> x
+            rewrite!("power-x1"; "(^ ?x 1)" => "?x"),
+            //inv(x) == x^(-1)
+            rewrite!("power-inv"; "(inv ?x)" => "(^ ?x (- 1))"),
+        ],
+    ]
+    .concat()
+}
@@ -0,0 +1,114 @@
+use crate::superinterpreter::*;
+use egg::*;
+
+define_language! {
+    pub enum WhileLanguage {
+        Bool(bool),
+        Num(i64),
+        Mem(Memory),
+        "eval" = Eval([Id; 2]),
+        "seq" = Seq([Id; 2]),
+        "=" = Assign([Id; 2]),
+        "if" = If([Id; 3]),
+        "while" = While([Id; 2]),
+        "<" = Lt([Id; 2]),
+        "+" = Add([Id; 2]),
+        "-" = Sub([Id; 2]),
+        "*" = Mul([Id; 2]),
+        "||" = Or([Id; 2]),
+        "&&" = And([Id; 2]),
+        "!!" = Not([Id; 1]),
+        Symbol(Symbol),
+    }
+}
+
+#[derive(Default)]
+pub struct WhileAnalysis;
+
+impl Analysis<WhileLanguage> for WhileAnalysis {
+    type Data = Option<Value>;
+
+    fn make(egraph: &EGraph<WhileLanguage, Self>, enode: &WhileLanguage) -> Self::Data {
+        let x = |i: &Id| egraph[*i].data.as_ref();
+        match enode {
+            WhileLanguage::Bool(b) => Some(Value::Bool(*b)),
+            WhileLanguage::Num(n) => Some(Value::Int(*n)),
+            WhileLanguage::Mem(m) => Some(Value::Mem(m.clone())),
+            WhileLanguage::Lt([a, b]) => fold("<", &[x(a), x(b)]),
+            WhileLanguage::Add([a, b]) => fold("+", &[x(a), x(b)]),
+            WhileLanguage::Sub([a, b]) => fold("-", &[x(a), x(b)]),
+            WhileLanguage::Mul([a, b]) => fold("*", &[x(a), x(b)]),
+            WhileLanguage::Or([a, b]) => fold("||", &[x(a), x(b)]),
+            WhileLanguage::And([a, b]) => fold("&&", &[x(a), x(b)]),
+            WhileLanguage::Not([a]) => fold("!!", &[x(a)]),
+            _ => None,
+        }
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_value(to, from)
+    }
+
+    fn modify(egraph: &mut EGraph<WhileLanguage, Self>, id: Id) {
+        if let Some(value) = egraph[id].data.clone() {
+            let added = add_value(egraph, &value);
+            egraph.union(id, added);
+        }
+    }
+}
+
+pub fn while_rules() -> Vec<Rewrite<WhileLanguage, WhileAnalysis>> {
+    vec![
+        // read_mem
+        // (v::Symbol, σ::Mem) => σ[v]
+        rewrite!("read-mem"; "(eval ?v ?mem)" => { ReadMem { var: "?v".parse().unwrap(), mem: "?mem".parse().unwrap() } }),
+
+        // arithm_rules
+        // (a + b, σ::Mem) --> (a, σ) + (b, σ)
+        rewrite!("eval-add"; "(eval (+ ?a ?b) ?mem)" => "(+ (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a * b, σ::Mem) --> (a, σ) * (b, σ)
+        rewrite!("eval-mul"; "(eval (* ?a ?b) ?mem)" => "(* (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a - b, σ::Mem) --> (a, σ) - (b, σ)
+        rewrite!("eval-sub"; "(eval (- ?a ?b) ?mem)" => "(- (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a::Union{Bool,Int}, σ::Mem) => a
+        rewrite!("eval-literal"; "(eval ?a ?mem)" => "?a" if is_literal("?a")),
+
+        // bool_rules
+        // (a < b, σ::Mem) --> (a, σ) < (b, σ)
+        rewrite!("eval-lt"; "(eval (< ?a ?b) ?mem)" => "(< (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a || b, σ::Mem) --> (a, σ) || (b, σ)
+        rewrite!("eval-or"; "(eval (|| ?a ?b) ?mem)" => "(|| (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a && b, σ::Mem) --> (a, σ) && (b, σ)
+        rewrite!("eval-and"; "(eval (&& ?a ?b) ?mem)" => "(&& (eval ?a ?mem) (eval ?b ?mem))"),
+        // (!(a), σ::Mem) --> !((a, σ))
+        rewrite!("eval-not"; "(eval (!! ?a) ?mem)" => "(!! (eval ?a ?mem))"),
+
+        // if_rules
+        // (if guard::Bool; t; else; f; end, σ::Mem) => guard ? (t, σ) : (f, σ)
+        rewrite!("eval-if"; "(eval (if ?guard ?t ?f) ?mem)" => {
+            EvalIf {
+                guard: "?guard".parse().unwrap(),
+                then: "?t".parse().unwrap(),
+                otherwise: "?f".parse().unwrap(),
+                mem: "?mem".parse().unwrap(),
+            }
+        }),
+
+        // while_rules
+        // (:skip, σ::Mem) --> σ
+        rewrite!("eval-skip"; "(eval skip ?mem)" => "?mem"),
+        // (while guard; body; end, σ::Mem) --> (if guard; body; while guard; body; end; end, σ)
+        rewrite!("eval-while"; "(eval (while ?guard ?body) ?mem)"
+            => "(eval (if ?guard (seq ?body (while ?guard ?body)) skip) ?mem)"),
+
+        // seq_rules
+        // ((a; b), σ::Mem) --> (b, (a, σ))
+        rewrite!("eval-seq"; "(eval (seq ?a ?b) ?mem)" => "(eval ?b (eval ?a ?mem))"),
+
+        // write_mem
+        // (sym::Symbol = val, σ::Mem) => (σ[sym] = (val, σ); σ)
+        rewrite!("write-mem"; "(eval (= ?x ?e) ?mem)" => {
+            WriteMem { var: "?x".parse().unwrap(), expr: "?e".parse().unwrap(), mem: "?mem".parse().unwrap() }
+        }),
+    ]
+}
@@ -0,0 +1,238 @@
+// Evaluators for the terms of the benchmark theories. They dispatch on the
+// printed operator, so they work for the `define_language!` variants, for
+// `SymbolLang` and for patterns, where variables are looked up as `?p`.
+
+use egg::*;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+fn root<L>(expr: &RecExpr<L>) -> Id {
+    Id::from(expr.as_ref().len() - 1)
+}
+
+/// Names of the leaves of `expr` other than `true` and `false`, in order of
+/// first occurrence.
+pub fn bool_vars<L: Language + Display>(expr: &RecExpr<L>) -> Vec<String> {
+    let mut vars = vec![];
+    for node in expr.as_ref() {
+        let name = node.to_string();
+        if node.is_leaf() && name != "true" && name != "false" && !vars.contains(&name) {
+            vars.push(name);
+        }
+    }
+    vars
+}
+
+/// All `2^n` assignments of `vars`.
+pub fn bool_assignments(vars: &[String]) -> impl Iterator<Item = HashMap<String, bool>> {
+    let vars = vars.to_vec();
+    (0..1u64 << vars.len()).map(move |bits| {
+        vars.iter().enumerate().map(|(i, v)| (v.clone(), bits & (1 << i) != 0)).collect()
+    })
+}
+
+fn eval_bool_at<L: Language + Display>(
+    expr: &RecExpr<L>,
+    id: Id,
+    env: &HashMap<String, bool>,
+) -> Option<bool> {
+    let node = &expr[id];
+    let arg = |i: usize| eval_bool_at(expr, node.children()[i], env);
+    match (node.to_string().as_str(), node.len()) {
+        ("true", 0) => Some(true),
+        ("false", 0) => Some(false),
+        (name, 0) => env.get(name).copied(),
+        ("!!", 1) => Some(!arg(0)?),
+        ("&&", 2) => Some(arg(0)? && arg(1)?),
+        ("||", 2) => Some(arg(0)? || arg(1)?),
+        ("==", 2) => Some(arg(0)? == arg(1)?),
+        ("!=", 2) => Some(arg(0)? != arg(1)?),
+        ("=>", 2) => Some(!arg(0)? || arg(1)?),
+        ("not", 1) => Some(!arg(0)?),
+        ("and", 2) => Some(arg(0)? && arg(1)?),
+        ("or", 2) => Some(arg(0)? || arg(1)?),
+        ("xor", 2) => Some(arg(0)? != arg(1)?),
+        ("mux", 3) => Some(if arg(0)? { arg(1)? } else { arg(2)? }),
+        _ => None,
+    }
+}
+
+/// Evaluates a `PropositionalLogic`, `CalcLogic` or `Circuit` term under `env`. Returns
+/// `None` for unknown operators and unassigned variables.
+pub fn eval_bool<L: Language + Display>(expr: &RecExpr<L>, env: &HashMap<String, bool>) -> Option<bool> {
+    eval_bool_at(expr, root(expr), env)
+}
+
+/// Whether `expr` evaluates to `true` under every assignment of its variables.
+pub fn is_tautology<L: Language + Display>(expr: &RecExpr<L>) -> bool {
+    let vars = bool_vars(expr);
+    bool_assignments(&vars).all(|env| eval_bool(expr, &env) == Some(true))
+}
+
+/// An exact rational number, kept normalised with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let g = gcd(num, den);
+        let sign = den.signum();
+        Some(Rational { num: sign * num / g, den: sign * den / g })
+    }
+
+    pub fn int(n: i128) -> Self {
+        Rational { num: n, den: 1 }
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let num = self.num.checked_mul(other.den)?.checked_add(other.num.checked_mul(self.den)?)?;
+        Rational::new(num, self.den.checked_mul(other.den)?)
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Rational::new(self.num.checked_mul(other.num)?, self.den.checked_mul(other.den)?)
+    }
+
+    pub fn checked_inv(self) -> Option<Self> {
+        Rational::new(self.den, self.num)
+    }
+
+    /// Only integer exponents are supported; `0^0` is `1` as in Julia.
+    pub fn checked_pow(self, exp: Self) -> Option<Self> {
+        if exp.den != 1 || exp.num.abs() > 64 {
+            return None;
+        }
+        let base = if exp.num < 0 { self.checked_inv()? } else { self };
+        let mut result = Rational::int(1);
+        for _ in 0..exp.num.abs() {
+            result = result.checked_mul(base)?;
+        }
+        Some(result)
+    }
+}
+
+impl std::ops::Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational { num: -self.num, den: self.den }
+    }
+}
+
+// Compares `a/b` with `c/d` for positive `b` and `d` without multiplying, which
+// could overflow: the integer parts are compared first, then the reciprocals of
+// the remainders, as in a continued fraction expansion.
+fn cmp_fractions(a: i128, b: i128, c: i128, d: i128) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    let (q1, r1) = (a.div_euclid(b), a.rem_euclid(b));
+    let (q2, r2) = (c.div_euclid(d), c.rem_euclid(d));
+    if q1 != q2 {
+        return q1.cmp(&q2);
+    }
+    match (r1, r2) {
+        (0, 0) => Ordering::Equal,
+        (0, _) => Ordering::Less,
+        (_, 0) => Ordering::Greater,
+        _ => cmp_fractions(d, r2, b, r1),
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        cmp_fractions(self.num, self.den, other.num, other.den)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::str::FromStr for Rational {
+    type Err = String;
+
+    /// Parses integers and fractions such as `-1/2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("not a rational: {}", s);
+        match s.split_once('/') {
+            Some((num, den)) => {
+                Rational::new(num.parse().map_err(|_| err())?, den.parse().map_err(|_| err())?).ok_or_else(err)
+            }
+            None => Ok(Rational::int(s.parse().map_err(|_| err())?)),
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(fmt, "{}", self.num)
+        } else {
+            write!(fmt, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Names of the non-numeric leaves of `expr`, in order of first occurrence.
+pub fn num_vars<L: Language + Display>(expr: &RecExpr<L>) -> Vec<String> {
+    let mut vars = vec![];
+    for node in expr.as_ref() {
+        let name = node.to_string();
+        if node.is_leaf() && name.parse::<i128>().is_err() && !vars.contains(&name) {
+            vars.push(name);
+        }
+    }
+    vars
+}
+
+fn eval_rational_at<L: Language + Display>(
+    expr: &RecExpr<L>,
+    id: Id,
+    env: &HashMap<String, Rational>,
+) -> Option<Rational> {
+    let node = &expr[id];
+    let arg = |i: usize| eval_rational_at(expr, node.children()[i], env);
+    match (node.to_string().as_str(), node.len()) {
+        (name, 0) => match name.parse::<i128>() {
+            Ok(n) => Some(Rational::int(n)),
+            Err(_) => env.get(name).copied(),
+        },
+        ("+", _) => node.children().iter().try_fold(Rational::int(0), |sum, c| {
+            sum.checked_add(eval_rational_at(expr, *c, env)?)
+        }),
+        ("-", 2) => arg(0)?.checked_add(-arg(1)?),
+        ("-", 1) => Some(-arg(0)?),
+        ("*", _) => node.children().iter().try_fold(Rational::int(1), |prod, c| {
+            prod.checked_mul(eval_rational_at(expr, *c, env)?)
+        }),
+        ("/", 2) => arg(0)?.checked_mul(arg(1)?.checked_inv()?),
+        ("^", 2) => arg(0)?.checked_pow(arg(1)?),
+        ("inv", 1) => arg(0)?.checked_inv(),
+        _ => None,
+    }
+}
+
+/// Evaluates a `BasicMath` or `VariadicMath` term under `env`. Returns `None` for unknown
+/// operators, unassigned variables, division by zero, non-integer exponents
+/// and overflow.
+pub fn eval_rational<L: Language + Display>(
+    expr: &RecExpr<L>,
+    env: &HashMap<String, Rational>,
+) -> Option<Rational> {
+    eval_rational_at(expr, root(expr), env)
+}
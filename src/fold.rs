@@ -0,0 +1,64 @@
+// Constant folding of boolean literals, the `fold` theory of Metatheory.jl's
+// calculational logic benchmark. The Julia rules are dynamic rules guarded by
+// `p::Bool`; here the applier looks for a `true` or `false` node in the
+// e-classes bound to the pattern variables.
+
+use egg::*;
+use std::fmt::Display;
+
+fn literal<L, N>(egraph: &EGraph<L, N>, id: Id) -> Option<bool>
+where
+    L: Language + Display,
+    N: Analysis<L>,
+{
+    egraph[id].nodes.iter().filter(|n| n.is_leaf()).find_map(|n| match n.to_string().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    })
+}
+
+/// Evaluates `op` on boolean literals.
+pub fn fold_bool(op: &str, args: &[bool]) -> Option<bool> {
+    match (op, args) {
+        ("==", [p, q]) => Some(p == q),
+        ("||", [p, q]) => Some(*p || *q),
+        ("=>", [p, q]) => Some((*p || *q) == *q),
+        ("&&", [p, q]) => Some(*p && *q),
+        ("!!", [p]) => Some(!p),
+        _ => None,
+    }
+}
+
+/// `(p::Bool op q::Bool) => (p op q)`
+pub struct FoldBool {
+    pub op: &'static str,
+    pub args: Vec<Var>,
+}
+
+impl FoldBool {
+    pub fn new(op: &'static str, args: &[&str]) -> Self {
+        FoldBool { op, args: args.iter().map(|v| v.parse().unwrap()).collect() }
+    }
+}
+
+impl<L, N> Applier<L, N> for FoldBool
+where
+    L: Language + FromOp + Display,
+    N: Analysis<L>,
+{
+    fn apply_one(&self, egraph: &mut EGraph<L, N>, eclass: Id, subst: &Subst, _: Option<&PatternAst<L>>, _: Symbol) -> Vec<Id> {
+        let args: Option<Vec<bool>> = self.args.iter().map(|v| literal(egraph, subst[*v])).collect();
+        match args.and_then(|args| fold_bool(self.op, &args)) {
+            Some(b) => {
+                let id = egraph.add(L::from_op(&b.to_string(), vec![]).unwrap());
+                if egraph.union(eclass, id) {
+                    vec![eclass]
+                } else {
+                    vec![]
+                }
+            }
+            None => vec![],
+        }
+    }
+}
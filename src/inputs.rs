@@ -0,0 +1,196 @@
+// The inputs of the benchmarks, with the names they have in the benchmark ids.
+// The benches of both languages and the snapshots in tests/sizes.rs read them
+// from here, so that a snapshot always covers what is benchmarked. The inputs
+// taken from Metatheory.jl are kept in its syntax and parsed with
+// `parse_julia`.
+
+use crate::bitvector::{constant_chain, mba_sum, shift_sum};
+use crate::circuits::{adder, comparator, parity};
+use crate::julia::{parse_julia_with, Fold};
+use crate::matrix::{chain_dims, matrix_chain};
+use crate::reachability::{chain, cycle, grid};
+use crate::relational::query;
+use crate::tautologies::{distributivity_tower, frege_chain, pigeonhole, syllogism_chain};
+use egg::*;
+
+fn julia(src: &str, fold: Fold) -> RecExpr<SymbolLang> {
+    parse_julia_with(src, fold).unwrap()
+}
+
+// The benchmarks were translated by hand before `parse_julia` existed, simpl1
+// nested to the right and simpl2 to the left.
+pub fn simpl1() -> String {
+    julia(":(a + b + (0 * c) + d)", Fold::Right).to_string()
+}
+
+pub fn simpl2() -> String {
+    julia(":(0 + (1 * foo * 0) + (a * 0) + a)", Fold::Left).to_string()
+}
+
+/// The inputs of basic_maths, which variadic_maths flattens.
+pub fn maths() -> Vec<(String, String)> {
+    vec![("simpl1".to_string(), simpl1()), ("simpl2".to_string(), simpl2())]
+}
+
+pub const DIVISION: [(&str, &str); 3] = [
+    ("div1", "(/ (* (+ a b) 3) 3)"),
+    ("div2", "(* (/ (^ x 2) (* 2 4)) (* 4 2))"),
+    ("div3", "(+ (/ (inv (inv 7)) 7) (/ (* a b) b))"),
+];
+
+// Metatheory.jl's `rewrite(ex, impl)`, which turns every `p ⟹ q` into
+// `!p || q`.
+fn rewrite_implications(expr: &RecExpr<SymbolLang>) -> RecExpr<SymbolLang> {
+    let mut rewritten = RecExpr::default();
+    let mut ids = vec![];
+    for node in expr.as_ref() {
+        let node = node.clone().map_children(|id| ids[usize::from(id)]);
+        let id = if node.op.as_str() == "=>" {
+            let not = rewritten.add(SymbolLang::new("!!", vec![node.children[0]]));
+            rewritten.add(SymbolLang::new("||", vec![not, node.children[1]]))
+        } else {
+            rewritten.add(node)
+        };
+        ids.push(id);
+    }
+    rewritten
+}
+
+// Julia nests `&&` and `||` to the right.
+pub fn ex_orig() -> String {
+    julia(":(((p ⟹ q) && (r ⟹ s) && (p || r)) ⟹ (q || s))", Fold::Right).to_string()
+}
+
+/// ex_orig with the implications rewritten, as Metatheory.jl proves it.
+pub fn prove1() -> String {
+    rewrite_implications(&ex_orig().parse().unwrap()).to_string()
+}
+
+pub fn demorgan() -> String {
+    julia(":(!(p || q) == (!p && !q))", Fold::Right).to_string()
+}
+
+pub fn freges_theorem() -> String {
+    julia(":((p ⟹ (q ⟹ r)) ⟹ ((p ⟹ q) ⟹ (p ⟹ r)))", Fold::Right).to_string()
+}
+
+/// Generates the member of a family of tautologies with parameter n.
+pub type Family = fn(usize) -> String;
+
+/// The families of tautologies, with the largest n the benchmark tries.
+pub const PROP_LOGIC_FAMILIES: [(&str, Family, usize); 4] = [
+    ("frege_chain", frege_chain, 8),
+    ("distributivity_tower", distributivity_tower, 16),
+    ("pigeonhole", pigeonhole, 4),
+    ("syllogism_chain", syllogism_chain, 12),
+];
+
+/// Goals from egg's math test suite, with a pattern of the derivative.
+pub const CALCULUS: [(&str, &str, &str); 7] = [
+    ("diff_simple1", "(d x (+ 1 (* 2 x)))", "2"),
+    ("diff_simple2", "(d x (+ 1 (* y x)))", "y"),
+    ("diff_ln", "(d x (ln x))", "(/ 1 x)"),
+    ("diff_power_simple", "(d x (pow x 3))", "(* 3 (pow x 2))"),
+    ("diff_power_harder", "(d x (- (pow x 3) (* 7 (pow x 2))))", "(* x (- (* 3 x) 14))"),
+    ("diff_chain", "(d x (cos (pow x 2)))", "(* -2 (* x (sin (pow x 2))))"),
+    ("simplify_const", "(+ 1 (- a (* (- 2 1) a)))", "1"),
+];
+
+/// Programs with a pattern of the value they reduce to. The Church numerals
+/// are applied to add1 and 0 to read off the result.
+pub const LAMBDA: [(&str, &str, &str); 5] = [
+    ("if", "(let zeroone (lam x (if (= (var x) 0) 0 1))
+              (+ (app (var zeroone) 0) (app (var zeroone) 10)))", "1"),
+    ("compose", "(let compose (lam f (lam g (lam x (app (var f) (app (var g) (var x))))))
+                 (let add1 (lam y (+ (var y) 1))
+                 (app (app (var compose) (var add1)) (var add1))))", "(lam ?x (+ (var ?x) 2))"),
+    ("compose_many", "(let compose (lam f (lam g (lam x (app (var f) (app (var g) (var x))))))
+                      (let add1 (lam y (+ (var y) 1))
+                      (app (app (var compose) (var add1))
+                           (app (app (var compose) (var add1))
+                                (app (app (var compose) (var add1))
+                                     (app (app (var compose) (var add1))
+                                          (app (app (var compose) (var add1))
+                                               (app (app (var compose) (var add1))
+                                                    (var add1)))))))))", "(lam ?x (+ (var ?x) 7))"),
+    ("church_plus", "(let zero (lam f (lam x (var x)))
+                     (let succ (lam n (lam f (lam x (app (var f) (app (app (var n) (var f)) (var x))))))
+                     (let plus (lam m (lam n (lam f (lam x (app (app (var m) (var f)) (app (app (var n) (var f)) (var x)))))))
+                     (app (app (app (app (var plus) (app (var succ) (var zero))) (app (var succ) (app (var succ) (var zero))))
+                               (lam y (+ (var y) 1)))
+                          0))))", "3"),
+    ("church_mul", "(let two (lam f (lam x (app (var f) (app (var f) (var x)))))
+                    (let mul (lam m (lam n (lam f (app (var m) (app (var n) (var f))))))
+                    (app (app (app (app (var mul) (var two)) (var two)) (lam y (+ (var y) 1))) 0)))", "4"),
+];
+
+/// Small enough for the rules to saturate; the xors of a parity tree have
+/// exponentially many groupings, and 8 inputs hit the node limit.
+pub fn circuits() -> Vec<(String, String)> {
+    vec![
+        ("adder_4".to_string(), adder(4)),
+        ("comparator_4".to_string(), comparator(4)),
+        ("parity_6".to_string(), parity(6)),
+    ]
+}
+
+/// Reassociation blows up with the length of the chain.
+pub fn matrix() -> Vec<(String, String)> {
+    let mut inputs: Vec<(String, String)> = [4, 8, 12, 16]
+        .iter()
+        .map(|n| (format!("chain_{}", n), matrix_chain(&chain_dims(*n))))
+        .collect();
+    inputs.push((
+        "scaled_transpose".to_string(),
+        "(scale s (transpose (* (* (mat A 50 1) (mat B 1 50)) (mat C 50 1))))".to_string(),
+    ));
+    inputs
+}
+
+/// Join reordering blows up with the number of tables.
+pub fn relational() -> Vec<(String, String)> {
+    let mut queries: Vec<(String, String)> = [2, 3, 4, 5]
+        .iter()
+        .map(|n| (format!("query_{}", n), query(*n)))
+        .collect();
+    queries.push((
+        "union".to_string(),
+        "(project (cols R.a) (select (and (< R.b 10) (= R.a S.a)) (join true (union (table R 1000) (table R 2000)) (table S 50))))".to_string(),
+    ));
+    queries
+}
+
+/// Every family grows with n.
+pub fn bitvector() -> Vec<(String, String)> {
+    let families = [
+        ("mba_sum", mba_sum as Family, [2, 4, 6]),
+        ("shift_sum", shift_sum, [4, 8, 12]),
+        ("constant_chain", constant_chain, [2, 4, 6]),
+    ];
+    let mut inputs = vec![];
+    for (family, generate, sizes) in families {
+        for n in sizes {
+            inputs.push((format!("{}_{}", family, n), generate(n)));
+        }
+    }
+    inputs
+}
+
+// exx = :((x = 0; while x < 10; x = x + 1; end; x), $(Mem(:x => 0)))
+pub const WHILE_10: &str = "(eval (seq (= x 0) (seq (while (< x 10) (= x (+ x 1))) x)) {x=0})";
+
+/// The facts of every graph, named after its shape and size.
+pub fn reachability() -> Vec<(String, Vec<String>)> {
+    let graphs = [
+        ("chain", chain as fn(usize) -> Vec<String>, [8, 16, 32]),
+        ("cycle", cycle, [4, 8, 16]),
+        ("grid", grid, [3, 4, 5]),
+    ];
+    let mut inputs = vec![];
+    for (graph, generate, sizes) in graphs {
+        for n in sizes {
+            inputs.push((format!("{}_{}", graph, n), generate(n)));
+        }
+    }
+    inputs
+}
@@ -0,0 +1,437 @@
+// Parser and printer for the Julia infix syntax used by the Metatheory.jl benchmarks, e.g.
+//
+//   :(((p ⟹ q) && (r ⟹ s) && (p || r)) ⟹ (q || s))
+//
+// Operators are mapped onto the names used by the egg theories (`!` becomes
+// `!!`, `⟹` becomes `=>`). Unparenthesised chains of `+`, `*`, `&&` and `||`
+// are folded into binary nodes in the direction given by `Fold`, since the
+// inputs of the existing benchmarks were translated by hand both ways:
+// basic_maths' simpl1 is nested to the right, simpl2 to the left.
+
+use egg::*;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JuliaParseError {
+    UnexpectedChar(char),
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    ChainedComparison,
+    UnknownOp(String),
+}
+
+impl fmt::Display for JuliaParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JuliaParseError::UnexpectedChar(c) => write!(fmt, "unexpected character {:?}", c),
+            JuliaParseError::UnexpectedToken(t) => write!(fmt, "unexpected token {:?}", t),
+            JuliaParseError::UnexpectedEnd => write!(fmt, "unexpected end of input"),
+            JuliaParseError::ChainedComparison => write!(fmt, "chained comparisons are not supported"),
+            JuliaParseError::UnknownOp(op) => write!(fmt, "operator {:?} is not part of the language", op),
+        }
+    }
+}
+
+impl std::error::Error for JuliaParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Ident(s) | Token::Int(s) => write!(fmt, "{}", s),
+            Token::Op(op) => write!(fmt, "{}", op),
+            Token::LParen => write!(fmt, "("),
+            Token::RParen => write!(fmt, ")"),
+            Token::Comma => write!(fmt, ","),
+        }
+    }
+}
+
+// Longest operators first so that `==` is not read as two `=`.
+const OPERATORS: [&str; 12] = ["-->", "||", "&&", "==", "!=", "⟹", "+", "-", "*", "/", "^", "!"];
+
+fn tokenize(src: &str) -> Result<Vec<Token>, JuliaParseError> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            tokens.push(Token::Int(chars[start..i].iter().collect()));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            let rest: String = chars[i..].iter().take(3).collect();
+            match OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+                Some(op) => {
+                    tokens.push(Token::Op(op));
+                    i += op.chars().count();
+                }
+                None => return Err(JuliaParseError::UnexpectedChar(c)),
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// Intermediate tree, lowered into a `RecExpr` once parsing succeeded.
+enum Term {
+    Atom(String),
+    Op(&'static str, Vec<Term>),
+    // An unparenthesised chain such as `a + b + c`, folded when lowering.
+    Chain(&'static str, Vec<Term>),
+    Group(Box<Term>),
+    Call(String, Vec<Term>),
+}
+
+/// The direction in which chains such as `a + b + c` are folded into binary
+/// nodes: `(+ (+ a b) c)` or `(+ a (+ b c))`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fold {
+    Left,
+    Right,
+}
+
+fn is_chain_op(op: &str) -> bool {
+    matches!(op, "+" | "*" | "&&" | "||")
+}
+
+// Binding powers follow Julia's operator precedence table.
+fn infix_binding_power(op: &str) -> Option<(u8, u8)> {
+    match op {
+        "⟹" | "-->" => Some((2, 1)),
+        "||" => Some((3, 4)),
+        "&&" => Some((5, 6)),
+        "==" | "!=" => Some((7, 8)),
+        "+" | "-" => Some((9, 10)),
+        "*" | "/" => Some((11, 12)),
+        "^" => Some((16, 15)),
+        _ => None,
+    }
+}
+
+const PREFIX_BINDING_POWER: u8 = 13;
+
+fn egg_op(op: &'static str) -> &'static str {
+    match op {
+        "⟹" | "-->" => "=>",
+        "!" => "!!",
+        _ => op,
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, JuliaParseError> {
+        let token = self.tokens.get(self.pos).cloned().ok_or(JuliaParseError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), JuliaParseError> {
+        let token = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(JuliaParseError::UnexpectedToken(token.to_string()))
+        }
+    }
+
+    fn expr(&mut self, min_bp: u8) -> Result<Term, JuliaParseError> {
+        let mut lhs = match self.next()? {
+            Token::Int(n) => Term::Atom(n),
+            Token::Ident(name) => {
+                if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1;
+                    Term::Call(name, self.args()?)
+                } else {
+                    Term::Atom(name)
+                }
+            }
+            Token::LParen => {
+                let inner = self.expr(0)?;
+                self.expect(Token::RParen)?;
+                Term::Group(Box::new(inner))
+            }
+            Token::Op(op @ ("-" | "!")) => {
+                let start = self.pos;
+                match self.expr(PREFIX_BINDING_POWER)? {
+                    // Julia reads `-1` as a negative literal, but `-(1)` as a negation.
+                    Term::Atom(n) if op == "-" && self.pos == start + 1 && matches!(self.tokens[start], Token::Int(_)) => {
+                        Term::Atom(format!("-{}", n))
+                    }
+                    operand => Term::Op(egg_op(op), vec![operand]),
+                }
+            }
+            token => return Err(JuliaParseError::UnexpectedToken(token.to_string())),
+        };
+
+        while let Some(Token::Op(op)) = self.peek() {
+            let op = *op;
+            let (l_bp, r_bp) = match infix_binding_power(op) {
+                Some(bp) => bp,
+                None => return Err(JuliaParseError::UnexpectedToken(op.to_string())),
+            };
+            if l_bp < min_bp {
+                break;
+            }
+            self.pos += 1;
+            let rhs = self.expr(r_bp)?;
+            if matches!(op, "==" | "!=") {
+                if let Some(Token::Op("==" | "!=")) = self.peek() {
+                    return Err(JuliaParseError::ChainedComparison);
+                }
+            }
+            lhs = match lhs {
+                Term::Chain(chain_op, mut args) if chain_op == op => {
+                    args.push(rhs);
+                    Term::Chain(chain_op, args)
+                }
+                lhs if is_chain_op(op) => Term::Chain(op, vec![lhs, rhs]),
+                lhs => Term::Op(egg_op(op), vec![lhs, rhs]),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn args(&mut self) -> Result<Vec<Term>, JuliaParseError> {
+        let mut args = vec![];
+        if self.peek() == Some(&Token::RParen) {
+            self.pos += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expr(0)?);
+            match self.next()? {
+                Token::Comma => continue,
+                Token::RParen => return Ok(args),
+                token => return Err(JuliaParseError::UnexpectedToken(token.to_string())),
+            }
+        }
+    }
+}
+
+fn lower<L: FromOp>(term: &Term, fold: Fold, expr: &mut RecExpr<L>) -> Result<Id, JuliaParseError> {
+    let unknown = |op: &str| JuliaParseError::UnknownOp(op.to_string());
+    match term {
+        Term::Group(inner) => lower(inner, fold, expr),
+        Term::Chain(op, args) => {
+            // Nodes are added in the order a hand-nested input would be parsed.
+            let node = |a, b| L::from_op(op, vec![a, b]).map_err(|_| unknown(op));
+            match fold {
+                Fold::Left => {
+                    let mut acc = lower(&args[0], fold, expr)?;
+                    for arg in &args[1..] {
+                        let id = lower(arg, fold, expr)?;
+                        acc = expr.add(node(acc, id)?);
+                    }
+                    Ok(acc)
+                }
+                Fold::Right => {
+                    let ids = args.iter().map(|a| lower(a, fold, expr)).collect::<Result<Vec<_>, _>>()?;
+                    let (last, init) = ids.split_last().unwrap();
+                    init.iter().rev().try_fold(*last, |acc, id| Ok(expr.add(node(*id, acc)?)))
+                }
+            }
+        }
+        Term::Atom(name) => {
+            let node = L::from_op(name, vec![]).map_err(|_| unknown(name))?;
+            Ok(expr.add(node))
+        }
+        Term::Op(op, args) => {
+            let ids = args.iter().map(|a| lower(a, fold, expr)).collect::<Result<Vec<_>, _>>()?;
+            let node = L::from_op(op, ids).map_err(|_| unknown(op))?;
+            Ok(expr.add(node))
+        }
+        Term::Call(f, args) => {
+            let ids = args.iter().map(|a| lower(a, fold, expr)).collect::<Result<Vec<_>, _>>()?;
+            // Languages without a dedicated operator for `f` may still encode
+            // function application as `(call f x)`.
+            match L::from_op(f, ids.clone()) {
+                Ok(node) => Ok(expr.add(node)),
+                Err(_) => {
+                    let head = L::from_op(f, vec![]).map_err(|_| unknown(f))?;
+                    let head = expr.add(head);
+                    let node = L::from_op("call", [vec![head], ids].concat()).map_err(|_| unknown(f))?;
+                    Ok(expr.add(node))
+                }
+            }
+        }
+    }
+}
+
+/// Parses a Julia expression such as `(p ⟹ q) || !p` into a `RecExpr` of any
+/// egg language. A surrounding quote `:( ... )` is accepted and ignored.
+/// Chains are folded to the left, see [`parse_julia_with`].
+pub fn parse_julia<L: FromOp>(src: &str) -> Result<RecExpr<L>, JuliaParseError> {
+    parse_julia_with(src, Fold::Left)
+}
+
+/// Like [`parse_julia`], but folds chains such as `a + b + c` in the direction
+/// given by `fold`.
+pub fn parse_julia_with<L: FromOp>(src: &str, fold: Fold) -> Result<RecExpr<L>, JuliaParseError> {
+    let src = src.trim();
+    let src = src.strip_prefix(':').unwrap_or(src);
+    let mut parser = Parser { tokens: tokenize(src)?, pos: 0 };
+    let term = parser.expr(0)?;
+    if let Some(token) = parser.peek() {
+        return Err(JuliaParseError::UnexpectedToken(token.to_string()));
+    }
+    let mut expr = RecExpr::default();
+    lower(&term, fold, &mut expr)?;
+    Ok(expr)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    Right,
+    None,
+}
+
+// Precedences are relative to each other only; they follow the same Julia
+// table as the parser's binding powers.
+const PREFIX_PRECEDENCE: u8 = 7;
+const ATOM_PRECEDENCE: u8 = u8::MAX;
+
+/// Prints a `RecExpr` in Julia infix syntax. Operators are looked up by their
+/// egg name in an operator table; nodes that are not in the table are printed
+/// as calls `f(x, y)`, and `(call f x)` as `f(x)`.
+pub struct InfixPrinter {
+    infix: HashMap<String, (String, u8, Assoc)>,
+    prefix: HashMap<String, String>,
+}
+
+impl Default for InfixPrinter {
+    fn default() -> Self {
+        InfixPrinter { infix: HashMap::default(), prefix: HashMap::default() }
+            .with_infix("=>", "⟹", 1, Assoc::Right)
+            .with_infix("||", "||", 2, Assoc::Left)
+            .with_infix("&&", "&&", 3, Assoc::Left)
+            .with_infix("==", "==", 4, Assoc::None)
+            .with_infix("!=", "!=", 4, Assoc::None)
+            .with_infix("+", "+", 5, Assoc::Left)
+            .with_infix("-", "-", 5, Assoc::Left)
+            .with_infix("*", "*", 6, Assoc::Left)
+            .with_infix("/", "/", 6, Assoc::Left)
+            .with_infix("^", "^", 8, Assoc::Right)
+            .with_prefix("!!", "!")
+            .with_prefix("-", "-")
+    }
+}
+
+impl InfixPrinter {
+    pub fn with_infix(mut self, op: &str, julia: &str, precedence: u8, assoc: Assoc) -> Self {
+        self.infix.insert(op.to_string(), (julia.to_string(), precedence, assoc));
+        self
+    }
+
+    pub fn with_prefix(mut self, op: &str, julia: &str) -> Self {
+        self.prefix.insert(op.to_string(), julia.to_string());
+        self
+    }
+
+    /// Prints the last node of `expr`; an empty `expr` prints as "".
+    pub fn print<L: Language + fmt::Display>(&self, expr: &RecExpr<L>) -> String {
+        match expr.as_ref().len() {
+            0 => String::new(),
+            n => self.node(expr, Id::from(n - 1)).0,
+        }
+    }
+
+    fn node<L: Language + fmt::Display>(&self, expr: &RecExpr<L>, id: Id) -> (String, u8) {
+        let node = &expr[id];
+        let op = node.to_string();
+        let children = node.children();
+        let args = |ids: &[Id]| ids.iter().map(|c| self.node(expr, *c).0).collect::<Vec<_>>().join(", ");
+
+        match children.len() {
+            0 if op.starts_with('-') => (op, PREFIX_PRECEDENCE),
+            0 => (op, ATOM_PRECEDENCE),
+            1 if self.prefix.contains_key(&op) => {
+                let julia = &self.prefix[&op];
+                let (arg, precedence) = self.node(expr, children[0]);
+                let child = &expr[children[0]];
+                let is_literal = child.is_leaf()
+                    && arg.starts_with(|c: char| c.is_ascii_digit())
+                    && arg.parse::<f64>().is_ok();
+                if julia == "-" && is_literal {
+                    // Julia reads `-1` back as the literal, which equals the negation.
+                    (format!("-{}", arg), PREFIX_PRECEDENCE)
+                } else if precedence < PREFIX_PRECEDENCE {
+                    (format!("{}({})", julia, arg), PREFIX_PRECEDENCE)
+                } else {
+                    (format!("{}{}", julia, arg), PREFIX_PRECEDENCE)
+                }
+            }
+            2 if self.infix.contains_key(&op) => {
+                let (julia, precedence, assoc) = &self.infix[&op];
+                let (lhs, lhs_precedence) = self.node(expr, children[0]);
+                let (rhs, rhs_precedence) = self.node(expr, children[1]);
+                let lhs = if lhs_precedence < *precedence
+                    || (lhs_precedence == *precedence && *assoc != Assoc::Left)
+                {
+                    format!("({})", lhs)
+                } else {
+                    lhs
+                };
+                let rhs = if rhs_precedence < *precedence
+                    || (rhs_precedence == *precedence && *assoc != Assoc::Right)
+                {
+                    format!("({})", rhs)
+                } else {
+                    rhs
+                };
+                (format!("{} {} {}", lhs, julia, rhs), *precedence)
+            }
+            _ if op == "call" => {
+                let head = self.node(expr, children[0]).0;
+                (format!("{}({})", head, args(&children[1..])), ATOM_PRECEDENCE)
+            }
+            _ => (format!("{}({})", op, args(children)), ATOM_PRECEDENCE),
+        }
+    }
+}
+
+/// Prints a `RecExpr` in Julia syntax using the operators of the benchmark
+/// theories, e.g. `(|| (!! p) q)` becomes `!p || q`.
+pub fn to_julia<L: Language + fmt::Display>(expr: &RecExpr<L>) -> String {
+    InfixPrinter::default().print(expr)
+}
@@ -0,0 +1,120 @@
+// Shared pieces of the lambda-calculus theory from egg's `lambda` test suite.
+// Substitution is explicit: `(let v e body)` is pushed into `body` by the
+// rules, and an analysis tracks the free variables of every e-class so that
+// substituting under a `lam` can rename the bound variable when it would
+// capture.
+
+use egg::*;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+#[derive(Debug, Clone)]
+pub struct LambdaData<L> {
+    /// The e-classes of the symbols that occur free in the class.
+    pub free: HashSet<Id>,
+    /// The number or boolean the class evaluates to, if known.
+    pub constant: Option<L>,
+}
+
+/// Free variables of `enode`, from those of its children.
+pub fn free_vars<L, N>(egraph: &EGraph<L, N>, enode: &L) -> HashSet<Id>
+where
+    L: Language + Display,
+    N: Analysis<L, Data = LambdaData<L>>,
+{
+    let f = |i: &Id| egraph[*i].data.free.iter().cloned();
+    let mut free = HashSet::default();
+    match (enode.to_string().as_str(), enode.children()) {
+        ("var", [v]) => {
+            free.insert(*v);
+        }
+        ("let", [v, a, b]) => {
+            free.extend(f(b));
+            free.remove(v);
+            free.extend(f(a));
+        }
+        ("lam" | "fix", [v, a]) => {
+            free.extend(f(a));
+            free.remove(v);
+        }
+        _ => enode.for_each(|c| free.extend(f(&c))),
+    }
+    free
+}
+
+pub fn merge_data<L: Language>(to: &mut LambdaData<L>, from: LambdaData<L>) -> DidMerge {
+    let before_len = to.free.len();
+    to.free.retain(|i| from.free.contains(i));
+    DidMerge(before_len != to.free.len(), to.free.len() != from.free.len())
+        | merge_option(&mut to.constant, from.constant, |a, b| {
+            debug_assert_eq!(*a, b, "merged e-classes with different constants");
+            DidMerge(false, false)
+        })
+}
+
+/// Adds the constant to the class of `id`, so that it can be extracted.
+pub fn add_constant<L, N>(egraph: &mut EGraph<L, N>, id: Id)
+where
+    L: Language,
+    N: Analysis<L, Data = LambdaData<L>>,
+{
+    if let Some(c) = egraph[id].data.constant.clone() {
+        let added = egraph.add(c);
+        egraph.union(id, added);
+    }
+}
+
+pub fn is_not_same_var<L, N>(v1: &str, v2: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L>,
+{
+    let v1: Var = v1.parse().unwrap();
+    let v2: Var = v2.parse().unwrap();
+    move |egraph, _, subst| egraph.find(subst[v1]) != egraph.find(subst[v2])
+}
+
+pub fn is_const<L, N>(v: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L, Data = LambdaData<L>>,
+{
+    let v: Var = v.parse().unwrap();
+    move |egraph, _, subst| egraph[subst[v]].data.constant.is_some()
+}
+
+/// `(let v1 e (lam v2 body))` with `v1 != v2`. If `v2` is free in `e`, the
+/// bound variable is renamed to a fresh symbol first.
+pub struct CaptureAvoid<L> {
+    pub fresh: Var,
+    pub v2: Var,
+    pub e: Var,
+    pub if_not_free: Pattern<L>,
+    pub if_free: Pattern<L>,
+}
+
+impl<L, N> Applier<L, N> for CaptureAvoid<L>
+where
+    L: Language + FromOp,
+    N: Analysis<L, Data = LambdaData<L>>,
+{
+    fn apply_one(
+        &self,
+        egraph: &mut EGraph<L, N>,
+        eclass: Id,
+        subst: &Subst,
+        searcher_ast: Option<&PatternAst<L>>,
+        rule_name: Symbol,
+    ) -> Vec<Id> {
+        let e = subst[self.e];
+        let v2 = subst[self.v2];
+        if egraph[e].data.free.contains(&v2) {
+            let mut subst = subst.clone();
+            let sym = L::from_op(&format!("_{}", eclass), vec![]).unwrap();
+            subst.insert(self.fresh, egraph.add(sym));
+            self.if_free.apply_one(egraph, eclass, &subst, searcher_ast, rule_name)
+        } else {
+            self.if_not_free.apply_one(egraph, eclass, subst, searcher_ast, rule_name)
+        }
+    }
+}
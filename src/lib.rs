@@ -1,5 +1,36 @@
 use egg::*;
 
+// The modules named after a theory hold its analyses, appliers and cost
+// functions. They are generic over the language, so that the theory's
+// `define_language!` variant in `customlang` and its `SymbolLang` variant in
+// `symbollang` share them.
+pub mod bitvector;
+pub mod calculus;
+pub mod circuits;
+pub mod customlang;
+pub mod eval;
+pub mod fold;
+pub mod inputs;
+pub mod julia;
+pub mod lambda;
+pub mod lint;
+pub mod matrix;
+pub mod nonzero;
+pub mod reachability;
+pub mod relational;
+pub mod sat;
+pub mod sorts;
+pub mod superinterpreter;
+pub mod symbollang;
+pub mod tautologies;
+pub mod term_rewriting;
+pub mod variadic;
+pub use eval::{bool_assignments, bool_vars, eval_bool, eval_rational, is_tautology, num_vars, Rational};
+pub use julia::{parse_julia, parse_julia_with, to_julia, Fold, InfixPrinter, JuliaParseError};
+pub use lint::{lint_rules, lint_rules_with_signature, Lint};
+pub use sat::{falsify, Unsupported, EXHAUSTIVE_VAR_LIMIT};
+pub use term_rewriting::rewrite_fixpoint;
+
 pub struct EGraphSize {
     pub num_classes: usize,
     pub num_memo: usize,
@@ -15,18 +46,23 @@ impl fmt::Display for EGraphSize {
 }
 
 
-pub fn simplify<L: Language>(
-    expr: &RecExpr<L>,
-    rules: &Vec<Rewrite<L, ()>>,
-    timeout: usize,
-) -> (RecExpr<L>, EGraphSize) {
-    // run rules
+/// The runner used by `simplify`, before the expression is added.
+pub fn simplify_runner<L: Language, N: Analysis<L> + Default>(timeout: usize) -> Runner<L, N> {
     let scheduler = BackoffScheduler::default();
-    let runner = Runner::default()
+    Runner::default()
         .with_iter_limit(timeout)
         .with_node_limit(15000)
-        .with_expr(&expr)
         .with_scheduler(scheduler)
+}
+
+pub fn simplify<L: Language, N: Analysis<L> + Default>(
+    expr: &RecExpr<L>,
+    rules: &Vec<Rewrite<L, N>>,
+    timeout: usize,
+) -> (RecExpr<L>, EGraphSize) {
+    // run rules
+    let runner = simplify_runner(timeout)
+        .with_expr(expr)
         .run(rules);
 
     // extract shortest expression
@@ -39,48 +75,191 @@ pub fn simplify<L: Language>(
     (best, size)
 }
 
-pub fn prove<L: Language>(
+/// Like `simplify`, but extracts with the cost function built by `cost`. Also
+/// returns the cost of `expr` and of the result, to report the quality of the
+/// optimisation. `cost` gets the e-graph so that costs can depend on analysis
+/// data; the cost of `expr` is taken before saturation.
+pub fn optimize<L, N, CF>(
     expr: &RecExpr<L>,
-    rules: &Vec<Rewrite<L, ()>>,
-    steps: usize,
+    rules: &Vec<Rewrite<L, N>>,
     timeout: usize,
+    cost: impl Fn(&EGraph<L, N>) -> CF,
+) -> (RecExpr<L>, (CF::Cost, CF::Cost), EGraphSize)
+where
+    L: Language,
+    N: Analysis<L> + Default,
+    CF: CostFunction<L>,
+{
+    let runner = simplify_runner(timeout).with_expr(expr);
+    let before = Extractor::new(&runner.egraph, cost(&runner.egraph)).find_best_cost(runner.roots[0]);
+    let runner = runner.run(rules);
+
+    let extractor = Extractor::new(&runner.egraph, cost(&runner.egraph));
+    let (after, best) = extractor.find_best(runner.roots[0]);
+    let size = EGraphSize {
+        num_classes: runner.egraph.classes().count(),
+        num_memo: runner.egraph.total_size(),
+        num_nodes: runner.egraph.total_number_of_nodes() };
+    (best, (before, after), size)
+}
+
+/// The runner used by `equivalent`, with `expr` added and, if it has no
+/// variables, `goal` as well, but not run yet.
+pub fn equivalent_runner<L: Language + 'static, N: Analysis<L> + Default>(
+    expr: &RecExpr<L>,
+    goal: &Pattern<L>,
+    timeout: usize,
+) -> Runner<L, N> {
+    let mut runner = simplify_runner(timeout).with_expr(expr);
+    // A goal without variables is added as well, so that the rules can meet it halfway.
+    if goal.vars().is_empty() {
+        let nodes: Vec<L> = goal.ast.as_ref().iter().filter_map(|n| match n {
+            ENodeOrVar::ENode(n) => Some(n.clone()),
+            ENodeOrVar::Var(_) => None,
+        }).collect();
+        runner = runner.with_expr(&RecExpr::from(nodes));
+    }
+    let target = goal.clone();
+    runner.with_hook(move |runner| {
+        if target.search_eclass(&runner.egraph, runner.roots[0]).is_some() {
+            Err("EQUIVALENT".to_string())
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Runs `simplify`'s runner on `expr` and stops as soon as `goal` matches in
+/// the class of `expr`, like egg's `test_fn!`. This is needed where the goal is
+/// not the smallest term of its class, or contains fresh names as in
+/// `(lam ?x (+ (var ?x) 2))`.
+pub fn equivalent<L: Language + 'static, N: Analysis<L> + Default>(
+    expr: &RecExpr<L>,
+    goal: &Pattern<L>,
+    rules: &Vec<Rewrite<L, N>>,
+    timeout: usize,
+) -> (bool, EGraphSize) {
+    let runner = equivalent_runner(expr, goal, timeout).run(rules);
+    let equal = goal.search_eclass(&runner.egraph, runner.roots[0]).is_some();
+    let size = EGraphSize {
+        num_classes: runner.egraph.classes().count(),
+        num_memo: runner.egraph.total_size(),
+        num_nodes: runner.egraph.total_number_of_nodes() };
+    (equal, size)
+}
+
+/// The runner used by every step of `prove`, before the expression and `tru`
+/// are added. It stops as soon as they are equal.
+pub fn prove_runner<L: Language, N: Analysis<L> + Default>(timeout: usize) -> Runner<L, N> {
+    let scheduler = BackoffScheduler::default()
+        .with_initial_match_limit(6000)
+        .with_ban_length(5);
+    Runner::default()
+        .with_iter_limit(timeout)
+        .with_node_limit(15000)
+        .with_scheduler(scheduler)
+        .with_hook(|runner| {
+            let istru =
+                runner.egraph.find(runner.roots[0]) == runner.egraph.find(runner.roots[1]);
+            // println!("Is true??? {}", istru);
+            if istru {
+                Err("PROVED".to_string())
+            } else {
+                Ok(())
+            }
+        })
+}
+
+/// The steps of `prove`, each with a runner built by `runner`, which `prove`
+/// does with `prove_runner`. Returns the runners that were run and the
+/// smallest term of the last one.
+pub fn prove_runs<L: Language, N: Analysis<L> + Default>(
+    expr: &RecExpr<L>,
+    rules: &Vec<Rewrite<L, N>>,
+    steps: usize,
     tru: &RecExpr<L>,
-) -> (RecExpr<L>, EGraphSize) {
-    let mut n_classes = 0;
-    let mut n_memo = 0;
-    let mut n_nodes = 0;
+    runner: impl Fn() -> Runner<L, N>,
+) -> (RecExpr<L>, Vec<Runner<L, N>>) {
+    let mut runners = vec![];
     // We start with the provided expr and in the following iterations start with the expr returned by the previous iteration.
     // As soon as expr == true all subsequent calls of .run() will return immediately because of the hook.
     let out: RecExpr<L> = (0..steps).fold(expr.clone(), |expr, _| {
-        let scheduler = BackoffScheduler::default()
-            .with_initial_match_limit(6000)
-            .with_ban_length(5);
-        let runner = Runner::default()
-            .with_iter_limit(timeout)
-            .with_node_limit(15000)
+        let runner = runner()
             .with_expr(&expr)
-            .with_expr(&tru)
-            .with_scheduler(scheduler)
-            .with_hook(|runner| {
-                let istru =
-                    runner.egraph.find(runner.roots[0]) == runner.egraph.find(runner.roots[1]);
-                // println!("Is true??? {}", istru);
-                if istru {
-                    Err("PROVED".to_string())
-                } else {
-                    Ok(())
-                }
-            })
+            .with_expr(tru)
             .run(rules);
         let root = runner.roots[0];
         let extractor = Extractor::new(&runner.egraph, AstSize);
         let (_, best) = extractor.find_best(root);
-        n_classes += runner.egraph.classes().count();
-        n_memo += runner.egraph.total_size();
-        n_nodes += runner.egraph.total_number_of_nodes();
+        runners.push(runner);
         best
     });
-    let size = EGraphSize { num_classes: n_classes, num_memo: n_memo, num_nodes: n_nodes };
+    (out, runners)
+}
+
+pub fn prove<L: Language, N: Analysis<L> + Default>(
+    expr: &RecExpr<L>,
+    rules: &Vec<Rewrite<L, N>>,
+    steps: usize,
+    timeout: usize,
+    tru: &RecExpr<L>,
+) -> (RecExpr<L>, EGraphSize) {
+    let (out, runners) = prove_runs(expr, rules, steps, tru, || prove_runner(timeout));
+    let size = EGraphSize {
+        num_classes: runners.iter().map(|r| r.egraph.classes().count()).sum(),
+        num_memo: runners.iter().map(|r| r.egraph.total_size()).sum(),
+        num_nodes: runners.iter().map(|r| r.egraph.total_number_of_nodes()).sum() };
 
     (out, size)
 }
+
+pub enum ProofResult<L: Language> {
+    Proved,
+    /// The goal is false under this assignment, so no amount of saturation proves it.
+    NotATautology(Vec<(String, bool)>),
+    /// The goal is a tautology, but egg stopped at this term.
+    OutOfBudget(RecExpr<L>),
+    /// egg stopped at this term, and the goal contains operators that are not
+    /// propositional, so whether it holds is not known.
+    Unknown(RecExpr<L>),
+}
+
+impl<L: Language + fmt::Display> fmt::Display for ProofResult<L> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProofResult::Proved => write!(fmt, "proved"),
+            ProofResult::NotATautology(assignment) => {
+                let assignment: Vec<String> = assignment.iter().map(|(v, b)| format!("{} = {}", v, b)).collect();
+                write!(fmt, "not a tautology, false for {}", assignment.join(", "))
+            }
+            ProofResult::OutOfBudget(expr) => write!(fmt, "out of budget at {}", to_julia(expr)),
+            ProofResult::Unknown(expr) => write!(fmt, "unknown, stopped at {}", to_julia(expr)),
+        }
+    }
+}
+
+/// Runs [`prove`] and, if the goal was not reduced to `tru`, searches for a
+/// falsifying assignment of the goal's variables to tell a goal that does not
+/// hold apart from one egg could not prove within its limits. Goals that are
+/// not purely propositional can only be reported as unknown.
+pub fn prove_or_refute<L: Language + fmt::Display>(
+    expr: &RecExpr<L>,
+    rules: &Vec<Rewrite<L, ()>>,
+    steps: usize,
+    timeout: usize,
+    tru: &RecExpr<L>,
+) -> (ProofResult<L>, EGraphSize) {
+    let (out, size) = prove(expr, rules, steps, timeout, tru);
+    if &out == tru {
+        return (ProofResult::Proved, size);
+    }
+    let result = match falsify(expr) {
+        Ok(Some(assignment)) => {
+            let vars = bool_vars(expr);
+            ProofResult::NotATautology(vars.iter().map(|v| (v.clone(), assignment[v])).collect())
+        }
+        Ok(None) => ProofResult::OutOfBudget(out),
+        Err(Unsupported) => ProofResult::Unknown(out),
+    };
+    (result, size)
+}
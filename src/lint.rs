@@ -0,0 +1,225 @@
+// Static checks over a rule set. Only rules whose searcher and applier are
+// plain patterns are inspected; rules with custom appliers are skipped.
+
+use egg::*;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint {
+    /// The right-hand side uses a variable that the left-hand side never binds.
+    UnboundVar { rule: Symbol, var: Var },
+    /// Both sides are identical to those of an earlier rule.
+    Duplicate { rule: Symbol, of: Symbol },
+    /// Same as an earlier rule up to renaming of the pattern variables.
+    AlphaEquivalent { rule: Symbol, of: Symbol },
+    /// A one-directional rule that is an instance of a bidirectional one.
+    Subsumed { rule: Symbol, by: Symbol },
+    /// The right-hand side is larger than the left-hand side and uses the same variables.
+    Expansive { rule: Symbol },
+    /// The left-hand side contains an operator that does not exist with this arity.
+    NeverMatches { rule: Symbol, op: String, arity: usize },
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lint::UnboundVar { rule, var } => write!(fmt, "{}: {} is not bound by the lhs", rule, var),
+            Lint::Duplicate { rule, of } => write!(fmt, "{}: duplicate of {}", rule, of),
+            Lint::AlphaEquivalent { rule, of } => write!(fmt, "{}: alpha-equivalent to {}", rule, of),
+            Lint::Subsumed { rule, by } => write!(fmt, "{}: subsumed by bidirectional {}", rule, by),
+            Lint::Expansive { rule } => write!(fmt, "{}: rhs is larger than lhs", rule),
+            Lint::NeverMatches { rule, op, arity } => {
+                write!(fmt, "{}: lhs uses {} with arity {}, which never matches", rule, op, arity)
+            }
+        }
+    }
+}
+
+struct PatternRule<'a, L> {
+    name: Symbol,
+    lhs: &'a PatternAst<L>,
+    rhs: &'a PatternAst<L>,
+}
+
+fn root<L>(ast: &RecExpr<L>) -> Id {
+    Id::from(ast.as_ref().len() - 1)
+}
+
+fn vars<L: Language>(ast: &PatternAst<L>) -> Vec<Var> {
+    let mut vars = vec![];
+    for node in ast.as_ref() {
+        if let ENodeOrVar::Var(v) = node {
+            if !vars.contains(v) {
+                vars.push(*v);
+            }
+        }
+    }
+    vars
+}
+
+// Number of nodes reachable from the root, counting shared subterms once per use.
+fn ast_size<L: Language>(ast: &PatternAst<L>, id: Id) -> usize {
+    1 + ast[id].children().iter().map(|c| ast_size(ast, *c)).sum::<usize>()
+}
+
+fn subterm<L: Language + fmt::Display>(ast: &PatternAst<L>, id: Id) -> String {
+    let node = &ast[id];
+    if node.is_leaf() {
+        node.to_string()
+    } else {
+        let children: Vec<String> = node.children().iter().map(|c| subterm(ast, *c)).collect();
+        format!("({} {})", node, children.join(" "))
+    }
+}
+
+// Renames the variables of both sides jointly, in order of first occurrence,
+// so that alpha-equivalent rules print the same.
+fn canonical<L: Language + fmt::Display>(rule: &PatternRule<L>) -> String {
+    let mut names = HashMap::<Var, String>::default();
+    for v in vars(rule.lhs).into_iter().chain(vars(rule.rhs)) {
+        let i = names.len();
+        names.entry(v).or_insert_with(|| format!("?{}", i));
+    }
+    let rename = |ast: &PatternAst<L>| {
+        let renamed: Vec<ENodeOrVar<L>> = ast.as_ref().iter().map(|n| match n {
+            ENodeOrVar::Var(v) => ENodeOrVar::Var(names[v].parse().unwrap()),
+            n => n.clone(),
+        }).collect();
+        let renamed = RecExpr::from(renamed);
+        subterm(&renamed, root(&renamed))
+    };
+    format!("{} => {}", rename(rule.lhs), rename(rule.rhs))
+}
+
+// Checks whether `term` is an instance of `pattern`, extending `subst` with
+// the subterms bound to the pattern's variables.
+fn instance<L: Language + fmt::Display>(
+    pattern: &PatternAst<L>,
+    pid: Id,
+    term: &PatternAst<L>,
+    tid: Id,
+    subst: &mut HashMap<Var, String>,
+) -> bool {
+    match (&pattern[pid], &term[tid]) {
+        (ENodeOrVar::Var(v), _) => {
+            let bound = subterm(term, tid);
+            *subst.entry(*v).or_insert_with(|| bound.clone()) == bound
+        }
+        (ENodeOrVar::ENode(p), ENodeOrVar::ENode(t)) if p.matches(t) => p
+            .children()
+            .iter()
+            .zip(t.children())
+            .all(|(pc, tc)| instance(pattern, *pc, term, *tc, subst)),
+        _ => false,
+    }
+}
+
+fn subsumes<L: Language + fmt::Display>(general: &PatternRule<L>, rule: &PatternRule<L>) -> bool {
+    let mut subst = HashMap::default();
+    instance(general.lhs, root(general.lhs), rule.lhs, root(rule.lhs), &mut subst)
+        && instance(general.rhs, root(general.rhs), rule.rhs, root(rule.rhs), &mut subst)
+}
+
+fn reverse<'a, L>(rule: &PatternRule<'a, L>) -> PatternRule<'a, L> {
+    PatternRule { name: rule.name, lhs: rule.rhs, rhs: rule.lhs }
+}
+
+fn pattern_rules<L: Language, N: Analysis<L>>(rules: &[Rewrite<L, N>]) -> Vec<PatternRule<'_, L>> {
+    rules
+        .iter()
+        .filter_map(|r| {
+            let lhs = r.searcher.get_pattern_ast()?;
+            let rhs = r.applier.get_pattern_ast()?;
+            Some(PatternRule { name: r.name, lhs, rhs })
+        })
+        .collect()
+}
+
+fn lint_patterns<L, N, F>(rules: &[Rewrite<L, N>], exists: F) -> Vec<Lint>
+where
+    L: Language + fmt::Display,
+    N: Analysis<L>,
+    F: Fn(&L) -> bool,
+{
+    let rules = pattern_rules(rules);
+    let mut lints = vec![];
+    let mut seen_exact = HashMap::<String, Symbol>::default();
+    let mut seen_alpha = HashMap::<String, Symbol>::default();
+    let canonical_forms: Vec<String> = rules.iter().map(canonical).collect();
+
+    for (i, rule) in rules.iter().enumerate() {
+        let lhs_vars = vars(rule.lhs);
+        let rhs_vars = vars(rule.rhs);
+        for var in &rhs_vars {
+            if !lhs_vars.contains(var) {
+                lints.push(Lint::UnboundVar { rule: rule.name, var: *var });
+            }
+        }
+
+        let exact = format!("{} => {}", rule.lhs, rule.rhs);
+        if let Some(of) = seen_exact.get(&exact) {
+            lints.push(Lint::Duplicate { rule: rule.name, of: *of });
+        } else if let Some(of) = seen_alpha.get(&canonical_forms[i]) {
+            lints.push(Lint::AlphaEquivalent { rule: rule.name, of: *of });
+        } else {
+            let bidirectional = |r: &PatternRule<L>| canonical_forms.contains(&canonical(&reverse(r)));
+            if !bidirectional(rule) {
+                let by = rules
+                    .iter()
+                    .enumerate()
+                    .find(|(j, other)| *j != i && bidirectional(other) && subsumes(other, rule));
+                if let Some((_, other)) = by {
+                    lints.push(Lint::Subsumed { rule: rule.name, by: other.name });
+                }
+            }
+        }
+        seen_exact.entry(exact).or_insert(rule.name);
+        seen_alpha.entry(canonical_forms[i].clone()).or_insert(rule.name);
+
+        let mut sorted_lhs_vars = lhs_vars.clone();
+        let mut sorted_rhs_vars = rhs_vars.clone();
+        sorted_lhs_vars.sort();
+        sorted_rhs_vars.sort();
+        if sorted_lhs_vars == sorted_rhs_vars
+            && ast_size(rule.rhs, root(rule.rhs)) > ast_size(rule.lhs, root(rule.lhs))
+        {
+            lints.push(Lint::Expansive { rule: rule.name });
+        }
+
+        for node in rule.lhs.as_ref() {
+            if let ENodeOrVar::ENode(node) = node {
+                if !exists(node) {
+                    let op = node.to_string();
+                    let arity = node.len();
+                    lints.push(Lint::NeverMatches { rule: rule.name, op, arity });
+                }
+            }
+        }
+    }
+    lints
+}
+
+/// Lints a rule set. The operators used by the patterns are valid by
+/// construction for languages defined with `define_language!`.
+pub fn lint_rules<L, N>(rules: &[Rewrite<L, N>]) -> Vec<Lint>
+where
+    L: Language + fmt::Display,
+    N: Analysis<L>,
+{
+    lint_patterns(rules, |_| true)
+}
+
+/// Like [`lint_rules`], but also checks every operator on the left-hand sides
+/// against the signature of the language `S`. This is useful for `SymbolLang`
+/// rules, which accept any operator with any number of children.
+pub fn lint_rules_with_signature<L, N, S>(rules: &[Rewrite<L, N>]) -> Vec<Lint>
+where
+    L: Language + fmt::Display,
+    N: Analysis<L>,
+    S: FromOp,
+{
+    lint_patterns(rules, |node| {
+        S::from_op(&node.to_string(), node.children().to_vec()).is_ok()
+    })
+}
@@ -0,0 +1,117 @@
+// Shared pieces of the matrix-chain theory. A matrix is declared in place as
+// `(mat A rows cols)`; `*` multiplies, `+` adds, `transpose` transposes and
+// `(scale s A)` scales by a scalar symbol. An analysis tracks the dimensions of
+// every e-class, and the FLOP cost function reads them, so the cheapest term
+// depends on the analysis and not just on the shape of the term.
+
+use egg::*;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The analysis data of an e-class: a dimension literal or the dimensions of
+/// a matrix. Classes of scalar symbols, names and ill-typed terms have `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Size(usize),
+    Matrix(usize, usize),
+}
+
+/// The shape of an operator applied to children of the shapes `args`.
+pub fn shape(op: &str, args: &[Option<Shape>]) -> Option<Shape> {
+    use Shape::*;
+    match (op, args) {
+        (_, []) => op.parse().ok().map(Size),
+        ("mat", [_, Some(Size(r)), Some(Size(c))]) => Some(Matrix(*r, *c)),
+        ("*", [Some(Matrix(m, k)), Some(Matrix(l, n))]) if k == l => Some(Matrix(*m, *n)),
+        ("+", [Some(Matrix(m, n)), Some(Matrix(k, l))]) if (m, n) == (k, l) => Some(Matrix(*m, *n)),
+        ("transpose", [Some(Matrix(m, n))]) => Some(Matrix(*n, *m)),
+        ("scale", [_, Some(Matrix(m, n))]) => Some(Matrix(*m, *n)),
+        _ => None,
+    }
+}
+
+pub fn merge_shape(to: &mut Option<Shape>, from: Option<Shape>) -> DidMerge {
+    merge_option(to, from, |a, b| {
+        debug_assert_eq!(*a, b, "merged e-classes with different shapes");
+        DidMerge(false, false)
+    })
+}
+
+/// Floating-point operations to evaluate a term, counting a multiply-add as
+/// one: `m * k * n` for a product of an `m x k` and a `k x n` matrix, `m * n`
+/// for a sum or scaling of `m x n` matrices. Transposes and leaves are free.
+///
+/// The shapes are read from the e-graph the cost function was built from, so
+/// it can only be used to extract from that e-graph.
+#[derive(Debug, Clone)]
+pub struct Flops {
+    shapes: HashMap<Id, Shape>,
+}
+
+impl Flops {
+    pub fn new<L, N>(egraph: &EGraph<L, N>) -> Self
+    where
+        L: Language,
+        N: Analysis<L, Data = Option<Shape>>,
+    {
+        let shapes = egraph.classes().filter_map(|class| Some((class.id, class.data?))).collect();
+        Flops { shapes }
+    }
+
+    pub fn flops<L: Language + Display>(&self, enode: &L) -> usize {
+        let dims = |id: &Id| match self.shapes.get(id) {
+            Some(Shape::Matrix(m, n)) => (*m, *n),
+            _ => (0, 0),
+        };
+        match (enode.to_string().as_str(), enode.children()) {
+            ("*", [a, b]) => dims(a).0 * dims(a).1 * dims(b).1,
+            ("+", [a, _]) | ("scale", [_, a]) => dims(a).0 * dims(a).1,
+            _ => 0,
+        }
+    }
+}
+
+impl<L: Language + Display> CostFunction<L> for Flops {
+    type Cost = usize;
+
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        enode.fold(self.flops(enode), |sum, id| sum.saturating_add(costs(id)))
+    }
+}
+
+/// The left-associated product of matrices `A0` to `A{n-1}`, where `Ai` is
+/// `dims[i] x dims[i + 1]`.
+pub fn matrix_chain(dims: &[usize]) -> String {
+    assert!(dims.len() > 1);
+    let mat = |i: usize| format!("(mat A{} {} {})", i, dims[i], dims[i + 1]);
+    (1..dims.len() - 1).fold(mat(0), |product, i| format!("(* {} {})", product, mat(i)))
+}
+
+/// Dimensions for a chain of `n` matrices, cycling through the textbook
+/// example of Cormen et al. so that the cheapest bracketing is not a
+/// left or right fold.
+pub fn chain_dims(n: usize) -> Vec<usize> {
+    const DIMS: [usize; 7] = [30, 35, 15, 5, 10, 20, 25];
+    (0..=n).map(|i| DIMS[i % DIMS.len()]).collect()
+}
+
+/// The cost of the cheapest bracketing of `matrix_chain(dims)`, by the usual
+/// dynamic programme.
+pub fn matrix_chain_flops(dims: &[usize]) -> usize {
+    let n = dims.len() - 1;
+    // best[i][j] is the cost of multiplying `Ai` to `Aj`
+    let mut best = vec![vec![0; n]; n];
+    for len in 1..n {
+        for i in 0..n - len {
+            let j = i + len;
+            best[i][j] = (i..j)
+                .map(|k| best[i][k] + best[k + 1][j] + dims[i] * dims[k + 1] * dims[j + 1])
+                .min()
+                .unwrap();
+        }
+    }
+    best[0][n - 1]
+}
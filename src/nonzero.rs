@@ -0,0 +1,120 @@
+// Division for `BasicMath`, in the customlang variant the separate language
+// `DivisionMath`, with rules that only hold for non-zero operands,
+// such as `x / x => 1`. The analysis tracks which e-classes are known to be
+// non-zero: non-zero literals, and products, quotients, powers, negations and
+// inverses of known non-zero values. Sums and variables are never known to be
+// non-zero. Like the evaluators it dispatches on the printed operator, so it
+// works for the `define_language!` variant and for `SymbolLang`.
+
+use egg::*;
+use std::fmt::Display;
+
+/// Whether `op` applied to children that are known to be non-zero as given by
+/// `args` is known to be non-zero.
+pub fn nonzero_of(op: &str, args: &[bool]) -> bool {
+    match (op, args) {
+        (_, []) => op.parse::<i64>().is_ok_and(|n| n != 0),
+        ("*" | "/", [a, b]) => *a && *b,
+        // x^n is non-zero for non-zero x, also for negative n
+        ("^", [a, _]) => *a,
+        ("-" | "inv", [a]) => *a,
+        _ => false,
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct NonZero;
+
+impl<L: Language + Display> Analysis<L> for NonZero {
+    type Data = bool;
+
+    fn make(egraph: &EGraph<L, Self>, enode: &L) -> Self::Data {
+        let args: Vec<bool> = enode.children().iter().map(|i| egraph[*i].data).collect();
+        nonzero_of(&enode.to_string(), &args)
+    }
+
+    // The classes are equal, so one of them being non-zero is enough.
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        let merged = *to || from;
+        let did_merge = DidMerge(merged != *to, merged != from);
+        *to = merged;
+        did_merge
+    }
+}
+
+/// Whether the class of `var` is known to be non-zero.
+pub fn is_nonzero<L, N>(var: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L, Data = bool>,
+{
+    let var: Var = var.parse().unwrap();
+    move |egraph, _, subst| egraph[subst[var]].data
+}
+
+// name, left-hand side, right-hand side, and the variable that must be
+// non-zero for the rule to hold
+const DIVISION_RULES: [(&str, &str, &str, Option<&str>); 10] = [
+    ("div-mul-inv", "(/ ?x ?y)", "(* ?x (inv ?y))", None),
+    ("div-1", "(/ ?x 1)", "?x", None),
+    ("div-self", "(/ ?x ?x)", "1", Some("?x")),
+    ("div-0", "(/ 0 ?x)", "0", Some("?x")),
+    ("div-cancel", "(/ (* ?x ?y) ?y)", "?x", Some("?y")),
+    ("mul-div", "(* (/ ?x ?y) ?y)", "?x", Some("?y")),
+    ("inv-cancel", "(* ?x (inv ?x))", "1", Some("?x")),
+    ("inv-inv", "(inv (inv ?x))", "?x", Some("?x")),
+    //inv(x) == x^(-1)
+    ("power-inv", "(inv ?x)", "(^ ?x (- 1))", Some("?x")),
+    //0^x --> 0, which is wrong for x = 0
+    ("power-0x", "(^ 0 ?x)", "0", Some("?x")),
+];
+
+/// The rules of `basic_maths_rules` that the division rules replace with
+/// conditional versions.
+pub const REPLACED_BY_DIVISION_RULES: [&str; 2] = ["power-inv", "power-0x"];
+
+/// The division rules, which replace `REPLACED_BY_DIVISION_RULES`. With
+/// `checked`, the rules that need a non-zero operand only apply where it is
+/// known to be non-zero; without, they always apply, which is unsound but
+/// measures the cost of the checks.
+pub fn division_rules<L, N>(checked: bool) -> Vec<Rewrite<L, N>>
+where
+    L: Language + FromOp + Display + Send + Sync + 'static,
+    N: Analysis<L, Data = bool> + 'static,
+{
+    DIVISION_RULES
+        .iter()
+        .map(|(name, lhs, rhs, var)| {
+            let lhs: Pattern<L> = lhs.parse().unwrap();
+            let rhs: Pattern<L> = rhs.parse().unwrap();
+            match var {
+                Some(var) if checked => {
+                    Rewrite::new(*name, lhs, ConditionalApplier { condition: is_nonzero(var), applier: rhs })
+                }
+                _ => Rewrite::new(*name, lhs, rhs),
+            }
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Copies the pattern rules among `rules` to the language `M`, which must have
+/// all of their operators, and the analysis `N`, dropping those named in
+/// `except`. Panics on rules whose sides are not patterns.
+pub fn with_analysis<L, M, N>(rules: &[Rewrite<L, ()>], except: &[&str]) -> Vec<Rewrite<M, N>>
+where
+    L: Language + Display,
+    M: Language + FromOp + Display + Send + Sync + 'static,
+    N: Analysis<M>,
+{
+    let pattern = |ast: &PatternAst<L>| ast.to_string().parse::<Pattern<M>>().unwrap();
+    rules
+        .iter()
+        .filter(|rule| !except.contains(&rule.name.as_str()))
+        .map(|rule| {
+            let lhs = pattern(rule.searcher.get_pattern_ast().expect("searcher is not a pattern"));
+            let rhs = pattern(rule.applier.get_pattern_ast().expect("applier is not a pattern"));
+            Rewrite::new(rule.name, lhs, rhs).unwrap()
+        })
+        .collect()
+}
@@ -0,0 +1,117 @@
+// Datalog-style reachability, to measure multi-patterns. Facts are terms such
+// as `(edge a b)` in the e-class of `true`, and a path is derived from an edge
+// and a path that meet in a node. The multi-pattern rule matches both facts
+// at once; the join rule matches the edge with an ordinary pattern and looks
+// up the paths in its applier, as rules had to before egg had multi-patterns.
+
+use crate::EGraphSize;
+use egg::*;
+use std::fmt::Display;
+
+/// The edges `x0 -> x1 -> ... -> x{n-1}`.
+pub fn chain(n: usize) -> Vec<String> {
+    (1..n).map(|i| format!("(edge x{} x{})", i - 1, i)).collect()
+}
+
+/// The edges of [`chain`] and `x{n-1} -> x0`, so that every node reaches
+/// every node.
+pub fn cycle(n: usize) -> Vec<String> {
+    let mut edges = chain(n);
+    edges.push(format!("(edge x{} x0)", n - 1));
+    edges
+}
+
+/// The edges of an `n` by `n` grid, from every node to its right and lower
+/// neighbours.
+pub fn grid(n: usize) -> Vec<String> {
+    let mut edges = vec![];
+    for i in 0..n {
+        for j in 0..n {
+            if j + 1 < n {
+                edges.push(format!("(edge x{}_{} x{}_{})", i, j, i, j + 1));
+            }
+            if i + 1 < n {
+                edges.push(format!("(edge x{}_{} x{}_{})", i, j, i + 1, j));
+            }
+        }
+    }
+    edges
+}
+
+/// `(path ?x ?z)` for every `(edge ?x ?y)` and `(path ?y ?z)` in the class of
+/// the edge, found through the parents of the class of `?y`.
+pub struct JoinPaths {
+    x: Var,
+    y: Var,
+}
+
+impl JoinPaths {
+    pub fn new(x: &str, y: &str) -> Self {
+        JoinPaths { x: x.parse().unwrap(), y: y.parse().unwrap() }
+    }
+}
+
+impl<L, N> Applier<L, N> for JoinPaths
+where
+    L: Language + FromOp + Display,
+    N: Analysis<L>,
+{
+    fn apply_one(&self, egraph: &mut EGraph<L, N>, eclass: Id, subst: &Subst, _: Option<&PatternAst<L>>, _: Symbol) -> Vec<Id> {
+        let facts = egraph.find(eclass);
+        let y = egraph.find(subst[self.y]);
+        let ends: Vec<Id> = egraph[y]
+            .parents()
+            .filter(|(node, id)| {
+                node.to_string() == "path" && egraph.find(node.children()[0]) == y && egraph.find(*id) == facts
+            })
+            .map(|(node, _)| node.children()[1])
+            .collect();
+        let mut changed = vec![];
+        for z in ends {
+            let path = egraph.add(L::from_op("path", vec![subst[self.x], z]).unwrap());
+            if egraph.union(facts, path) {
+                changed.push(facts);
+            }
+        }
+        changed
+    }
+}
+
+/// The runner used by `derive`, with `facts` added to its e-graph and equal to
+/// `true`, but not run yet, and the id of `true`.
+pub fn derive_runner<L, N>(facts: &[String], iter_limit: usize) -> (Runner<L, N>, Id)
+where
+    L: Language + FromOp,
+    N: Analysis<L> + Default,
+{
+    let mut egraph: EGraph<L, N> = EGraph::default();
+    let tru = egraph.add(L::from_op("true", vec![]).unwrap());
+    for fact in facts {
+        let id = egraph.add_expr(&fact.parse().unwrap());
+        egraph.union(tru, id);
+    }
+    egraph.rebuild();
+    let runner = Runner::default()
+        .with_iter_limit(iter_limit)
+        .with_node_limit(100_000)
+        .with_egraph(egraph);
+    (runner, tru)
+}
+
+/// Adds `facts` to an e-graph, makes them equal to `true` and runs `rules` to
+/// saturation. Returns the number of `path` facts and the size of the e-graph.
+pub fn derive<L, N>(facts: &[String], rules: &[Rewrite<L, N>], iter_limit: usize) -> (usize, EGraphSize)
+where
+    L: Language + FromOp + Display,
+    N: Analysis<L> + Default,
+{
+    let (runner, tru) = derive_runner(facts, iter_limit);
+    let runner = runner.run(rules);
+    let tru = runner.egraph.find(tru);
+    let paths = runner.egraph[tru].nodes.iter().filter(|n| n.to_string() == "path").count();
+    let size = EGraphSize {
+        num_classes: runner.egraph.classes().count(),
+        num_memo: runner.egraph.total_size(),
+        num_nodes: runner.egraph.total_number_of_nodes() };
+    (paths, size)
+}
@@ -0,0 +1,152 @@
+// Shared pieces of the relational-algebra theory. A table is declared in place
+// as `(table R rows)` and its columns are written `R.a`. Relations are built
+// with `select`, `project`, `join` (a theta join, `(join true r s)` is the
+// cross product) and `union`; predicates with `=`, `<`, `and`, `or` and `not`.
+// An analysis tracks the tables every class draws from or refers to, and an
+// estimate of the rows of a relation or the selectivity of a predicate, from
+// the table sizes and the default selectivities of System R. The cost of a
+// plan is the work of a naive executor on these estimates.
+
+use egg::*;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelData {
+    /// The tables of a relation, or the tables whose columns a predicate or
+    /// column list refers to.
+    pub tables: BTreeSet<String>,
+    /// The rows of a relation, the selectivity of a predicate or the value of
+    /// a number.
+    pub estimate: Option<f64>,
+}
+
+/// The data of a leaf: numbers are values, `true` always holds, and any other
+/// symbol refers to the table before its first `.`.
+pub fn leaf_data(op: &str) -> RelData {
+    let mut tables = BTreeSet::new();
+    let estimate = if let Ok(n) = op.parse::<f64>() {
+        Some(n)
+    } else if op == "true" {
+        Some(1.0)
+    } else {
+        tables.insert(op.split('.').next().unwrap().to_string());
+        None
+    };
+    RelData { tables, estimate }
+}
+
+/// The estimate of an operator applied to children with the estimates `args`.
+pub fn estimate(op: &str, args: &[Option<f64>]) -> Option<f64> {
+    match (op, args) {
+        ("table", [_, n]) => *n,
+        ("select", [Some(p), Some(r)]) => Some(p * r),
+        ("join", [Some(p), Some(r), Some(s)]) => Some(p * r * s),
+        ("project", [_, r]) => *r,
+        ("union", [Some(r), Some(s)]) => Some(r + s),
+        ("=", [_, _]) => Some(0.1),
+        ("<", [_, _]) => Some(1.0 / 3.0),
+        ("and", [Some(p), Some(q)]) => Some(p * q),
+        ("or", [Some(p), Some(q)]) => Some(p + q - p * q),
+        ("not", [Some(p)]) => Some(1.0 - p),
+        _ => None,
+    }
+}
+
+/// The data of an operator from that of its children.
+pub fn make_data(op: &str, args: &[&RelData]) -> RelData {
+    let tables = match (op, args) {
+        ("select" | "project", [_, r]) => r.tables.clone(),
+        ("join", [_, r, s]) => r.tables.union(&s.tables).cloned().collect(),
+        _ => args.iter().flat_map(|a| a.tables.iter().cloned()).collect(),
+    };
+    let estimates: Vec<Option<f64>> = args.iter().map(|a| a.estimate).collect();
+    RelData { tables, estimate: estimate(op, &estimates) }
+}
+
+/// Equal classes draw from the same tables, but their estimates can differ
+/// with the order in which selectivities were multiplied; keeps the smaller.
+pub fn merge_data(to: &mut RelData, from: RelData) -> DidMerge {
+    debug_assert_eq!(to.tables, from.tables, "merged e-classes with different tables");
+    merge_option(&mut to.estimate, from.estimate, |a, b| {
+        if b < *a {
+            *a = b;
+            DidMerge(true, false)
+        } else {
+            DidMerge(false, *a < b)
+        }
+    })
+}
+
+/// Whether the predicate `p` only refers to tables of the relation `r`, so
+/// that a selection by `p` can be pushed into `r`.
+pub fn refers_only_to<L, N>(p: &str, r: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L, Data = RelData>,
+{
+    let p: Var = p.parse().unwrap();
+    let r: Var = r.parse().unwrap();
+    move |egraph, _, subst| egraph[subst[p]].data.tables.is_subset(&egraph[subst[r]].data.tables)
+}
+
+/// The work of a naive executor, summed over the plan: every operator reads
+/// its input rows, except that a join compares every pair of rows, and writes
+/// its output rows. Predicates and column lists are free.
+///
+/// The estimates are read from the e-graph the cost function was built from,
+/// so it can only be used to extract from that e-graph.
+#[derive(Debug, Clone)]
+pub struct Work {
+    data: HashMap<Id, RelData>,
+}
+
+impl Work {
+    pub fn new<L, N>(egraph: &EGraph<L, N>) -> Self
+    where
+        L: Language,
+        N: Analysis<L, Data = RelData>,
+    {
+        let data = egraph.classes().map(|class| (class.id, class.data.clone())).collect();
+        Work { data }
+    }
+
+    pub fn work<L: Language + Display>(&self, enode: &L) -> f64 {
+        let op = enode.to_string();
+        let args: Vec<Option<f64>> = enode.children().iter().map(|id| self.data[id].estimate).collect();
+        let read = match (op.as_str(), args.as_slice()) {
+            ("table", _) => 0.0,
+            ("select" | "project", [_, Some(r)]) => *r,
+            ("join", [_, Some(r), Some(s)]) => r * s,
+            ("union", [Some(r), Some(s)]) => r + s,
+            _ => return 0.0,
+        };
+        read + estimate(&op, &args).unwrap_or(f64::INFINITY)
+    }
+}
+
+impl<L: Language + Display> CostFunction<L> for Work {
+    type Cost = f64;
+
+    fn cost<C>(&mut self, enode: &L, mut costs: C) -> Self::Cost
+    where
+        C: FnMut(Id) -> Self::Cost,
+    {
+        enode.fold(self.work(enode), |sum, id| sum + costs(id))
+    }
+}
+
+/// An unoptimised query over the tables `T0` to `T{n-1}`: one selection over
+/// their left-deep cross product, joining neighbouring tables on a key and
+/// filtering every other table by a constant. Table sizes grow with the index,
+/// so the best plan filters first and joins the small tables first.
+pub fn query(n: usize) -> String {
+    assert!(n > 0);
+    let table = |i: usize| format!("(table T{} {})", i, 100 * (i + 1) * (i + 1));
+    let product = (1..n).fold(table(0), |r, i| format!("(join true {} {})", r, table(i)));
+    let predicates = (1..n)
+        .map(|i| format!("(= T{}.k T{}.k)", i - 1, i))
+        .chain((0..n).step_by(2).map(|i| format!("(< T{}.v 10)", i)));
+    let predicate = predicates.reduce(|p, q| format!("(and {} {})", p, q)).unwrap();
+    format!("(select {} {})", predicate, product)
+}
@@ -0,0 +1,185 @@
+// Search for assignments that falsify a propositional term. Small terms are
+// checked exhaustively with the evaluator; larger ones are translated to CNF
+// (Tseitin encoding of the negated term) and handed to a plain DPLL solver.
+
+use crate::eval::{bool_assignments, bool_vars, eval_bool};
+use egg::*;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+/// Terms with more variables than this are refuted with DPLL instead of
+/// enumerating all assignments.
+pub const EXHAUSTIVE_VAR_LIMIT: usize = 12;
+
+// A literal is a variable index (starting at 1), negative when negated.
+type Clause = Vec<i32>;
+
+struct Cnf {
+    clauses: Vec<Clause>,
+    num_vars: i32,
+}
+
+impl Cnf {
+    fn fresh(&mut self) -> i32 {
+        self.num_vars += 1;
+        self.num_vars
+    }
+}
+
+// Adds the clauses defining each subterm of `expr` and returns the literal of
+// every node. Returns `None` for operators that are not propositional.
+fn tseitin<L: Language + Display>(
+    expr: &RecExpr<L>,
+    vars: &HashMap<String, i32>,
+    cnf: &mut Cnf,
+) -> Option<Vec<i32>> {
+    let mut lits: Vec<i32> = vec![];
+    for node in expr.as_ref() {
+        let arg = |i: usize| lits[usize::from(node.children()[i])];
+        let lit = match (node.to_string().as_str(), node.len()) {
+            ("true", 0) => {
+                let x = cnf.fresh();
+                cnf.clauses.push(vec![x]);
+                x
+            }
+            ("false", 0) => {
+                let x = cnf.fresh();
+                cnf.clauses.push(vec![-x]);
+                x
+            }
+            (name, 0) => vars[name],
+            ("!!", 1) => -arg(0),
+            (op @ ("&&" | "||" | "==" | "!=" | "=>"), 2) => {
+                let (a, b) = (arg(0), arg(1));
+                let x = cnf.fresh();
+                let gate: Vec<Clause> = match op {
+                    "&&" => vec![vec![-x, a], vec![-x, b], vec![x, -a, -b]],
+                    "||" => vec![vec![x, -a], vec![x, -b], vec![-x, a, b]],
+                    "=>" => vec![vec![x, a], vec![x, -b], vec![-x, -a, b]],
+                    "==" => vec![vec![-x, -a, b], vec![-x, a, -b], vec![x, a, b], vec![x, -a, -b]],
+                    _ => vec![vec![x, -a, b], vec![x, a, -b], vec![-x, a, b], vec![-x, -a, -b]],
+                };
+                cnf.clauses.extend(gate);
+                x
+            }
+            _ => return None,
+        };
+        lits.push(lit);
+    }
+    Some(lits)
+}
+
+fn dpll(clauses: &[Clause], num_vars: i32, assignment: &mut HashMap<i32, bool>) -> bool {
+    let value = |lit: i32, assignment: &HashMap<i32, bool>| {
+        assignment.get(&lit.abs()).map(|v| *v == (lit > 0))
+    };
+
+    // unit propagation
+    let mut trail = vec![];
+    loop {
+        let mut unit = None;
+        for clause in clauses {
+            let mut unassigned = vec![];
+            let mut satisfied = false;
+            for lit in clause {
+                match value(*lit, assignment) {
+                    Some(true) => {
+                        satisfied = true;
+                        break;
+                    }
+                    Some(false) => {}
+                    None => unassigned.push(*lit),
+                }
+            }
+            if satisfied {
+                continue;
+            }
+            match unassigned.len() {
+                0 => {
+                    for v in trail {
+                        assignment.remove(&v);
+                    }
+                    return false;
+                }
+                1 => {
+                    unit = Some(unassigned[0]);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        match unit {
+            Some(lit) => {
+                assignment.insert(lit.abs(), lit > 0);
+                trail.push(lit.abs());
+            }
+            None => break,
+        }
+    }
+
+    // Branch on the unassigned variable closest to the root. Tseitin variables
+    // are numbered bottom-up, so this splits on whole subterms before inputs,
+    // which refutes e.g. long conjunctions of tautologies without backtracking.
+    let branch = (1..=num_vars).rev().find(|var| !assignment.contains_key(var));
+    let result = match branch {
+        None => true,
+        Some(var) => {
+            [true, false].iter().any(|v| {
+                assignment.insert(var, *v);
+                let sat = dpll(clauses, num_vars, assignment);
+                if !sat {
+                    assignment.remove(&var);
+                }
+                sat
+            })
+        }
+    };
+    if !result {
+        for v in trail {
+            assignment.remove(&v);
+        }
+    }
+    result
+}
+
+/// `expr` contains operators that are not propositional, so it can be
+/// neither refuted nor shown to be a tautology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsupported;
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "term contains operators that are not propositional")
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// Looks for an assignment of the variables of `expr` under which it is
+/// false. Returns `Ok(None)` if there is none, i.e. if `expr` is a tautology,
+/// and `Err(Unsupported)` if `expr` contains operators that are not
+/// propositional.
+pub fn falsify<L: Language + Display>(expr: &RecExpr<L>) -> Result<Option<HashMap<String, bool>>, Unsupported> {
+    let names = bool_vars(expr);
+    if names.len() <= EXHAUSTIVE_VAR_LIMIT {
+        for env in bool_assignments(&names) {
+            match eval_bool(expr, &env) {
+                Some(false) => return Ok(Some(env)),
+                Some(true) => {}
+                None => return Err(Unsupported),
+            }
+        }
+        return Ok(None);
+    }
+
+    let vars: HashMap<String, i32> = names.iter().zip(1..).map(|(n, i)| (n.clone(), i)).collect();
+    let mut cnf = Cnf { clauses: vec![], num_vars: names.len() as i32 };
+    let lits = tseitin(expr, &vars, &mut cnf).ok_or(Unsupported)?;
+    cnf.clauses.push(vec![-lits[lits.len() - 1]]);
+
+    let mut assignment = HashMap::default();
+    if !dpll(&cnf.clauses, cnf.num_vars, &mut assignment) {
+        return Ok(None);
+    }
+    Ok(Some(names.iter().map(|n| (n.clone(), assignment[&vars[n]])).collect()))
+}
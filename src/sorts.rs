@@ -0,0 +1,104 @@
+// Sorts of e-classes, for theories whose Julia rules carry type guards such as
+// `p::Bool`. The analysis dispatches on the printed operator, like the
+// evaluators, so it works for the `define_language!` variants and for
+// `SymbolLang`. A class whose sort is not known, such as a variable, is an
+// `Expr`; rules restricted to a sort still apply to it, but not to classes
+// known to have another sort.
+
+use egg::*;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    Bool,
+    Int,
+    Expr,
+}
+
+impl Sort {
+    /// Whether a class of this sort may have sort `sort`.
+    pub fn may_be(self, sort: Sort) -> bool {
+        self == sort || self == Sort::Expr
+    }
+}
+
+/// The sort of `op` applied to children of sort `args`. Logical operators
+/// give a `Bool` whatever their children, integer literals and `+`, `-`
+/// and `*` of `Int`s give an `Int`, and anything else an `Expr`.
+pub fn sort_of(op: &str, args: &[Sort]) -> Sort {
+    match op {
+        "true" | "false" if args.is_empty() => Sort::Bool,
+        "==" | "!=" | "||" | "&&" | "!!" | "=>" => Sort::Bool,
+        "+" | "-" | "*" if args.iter().all(|s| *s == Sort::Int) => Sort::Int,
+        _ if args.is_empty() && op.parse::<i64>().is_ok() => Sort::Int,
+        _ => Sort::Expr,
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Sorts;
+
+impl<L: Language + Display> Analysis<L> for Sorts {
+    type Data = Sort;
+
+    fn make(egraph: &EGraph<L, Self>, enode: &L) -> Self::Data {
+        let args: Vec<Sort> = enode.children().iter().map(|i| egraph[*i].data).collect();
+        sort_of(&enode.to_string(), &args)
+    }
+
+    // An `Expr` learns the sort of the class it is merged with. Merging a
+    // `Bool` with an `Int` only happens through an ill-sorted rule, and keeps
+    // the sort of `to`.
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        if *to == from {
+            DidMerge(false, false)
+        } else if *to == Sort::Expr {
+            *to = from;
+            DidMerge(true, false)
+        } else {
+            DidMerge(false, true)
+        }
+    }
+}
+
+/// Whether the class of `var` has sort `sort`, like Julia's `var::Bool`.
+pub fn is_sort<L, N>(var: &str, sort: Sort) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L, Data = Sort>,
+{
+    let var: Var = var.parse().unwrap();
+    move |egraph, _, subst| egraph[subst[var]].data == sort
+}
+
+/// Whether the class of `var` may have sort `sort`, i.e. has it or is an
+/// `Expr`. This rejects ill-sorted matches but keeps those on variables.
+pub fn may_be_sort<L, N>(var: &str, sort: Sort) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L, Data = Sort>,
+{
+    let var: Var = var.parse().unwrap();
+    move |egraph, _, subst| egraph[subst[var]].data.may_be(sort)
+}
+
+/// Copies `rules` to the `Sorts` analysis, applying each only where every
+/// pattern variable may have sort `sort`. Panics on rules whose sides are not
+/// patterns.
+pub fn restrict<L>(rules: &[Rewrite<L, ()>], sort: Sort) -> Vec<Rewrite<L, Sorts>>
+where
+    L: Language + Display + Send + Sync + 'static,
+{
+    rules
+        .iter()
+        .map(|rule| {
+            let lhs = Pattern::new(rule.searcher.get_pattern_ast().expect("searcher is not a pattern").clone());
+            let rhs = Pattern::new(rule.applier.get_pattern_ast().expect("applier is not a pattern").clone());
+            let vars = lhs.vars();
+            let condition = move |egraph: &mut EGraph<L, Sorts>, _: Id, subst: &Subst| {
+                vars.iter().all(|v| egraph[subst[*v]].data.may_be(sort))
+            };
+            Rewrite::new(rule.name, lhs, ConditionalApplier { condition, applier: rhs }).unwrap()
+        })
+        .collect()
+}
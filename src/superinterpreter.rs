@@ -0,0 +1,252 @@
+// Shared pieces of the While-language superinterpreter theory from
+// Metatheory.jl's `while_superinterpreter` benchmark. A program `p` run in
+// memory `σ` is written `(eval p σ)`; saturating the e-graph interprets it.
+//
+// The Julia theory relies on dynamic rules with type guards, e.g.
+// `(v::Symbol, σ::Mem) => σ[v]`. Here the values of e-classes (integers,
+// booleans and memories) are tracked by an analysis, and the dynamic rules
+// are appliers that read it.
+
+use egg::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// A memory mapping variable names to integers, written `{x=0,y=1}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Memory(Vec<(String, i64)>);
+
+impl Memory {
+    pub fn get(&self, var: &str) -> Option<i64> {
+        self.0.iter().find(|(v, _)| v == var).map(|(_, n)| *n)
+    }
+
+    pub fn set(&self, var: &str, n: i64) -> Memory {
+        let mut vars = self.0.clone();
+        match vars.iter_mut().find(|(v, _)| v == var) {
+            Some(entry) => entry.1 = n,
+            None => {
+                vars.push((var.to_string(), n));
+                vars.sort();
+            }
+        }
+        Memory(vars)
+    }
+}
+
+impl fmt::Display for Memory {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let vars: Vec<String> = self.0.iter().map(|(v, n)| format!("{}={}", v, n)).collect();
+        write!(fmt, "{{{}}}", vars.join(","))
+    }
+}
+
+impl FromStr for Memory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .ok_or_else(|| format!("not a memory: {}", s))?;
+        let mut memory = Memory::default();
+        for entry in inner.split(',').filter(|e| !e.is_empty()) {
+            let (var, n) = entry.split_once('=').ok_or_else(|| format!("bad entry: {}", entry))?;
+            let n = n.parse().map_err(|_| format!("bad value: {}", entry))?;
+            memory = memory.set(var, n);
+        }
+        Ok(memory)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Bool(bool),
+    Mem(Memory),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(fmt, "{}", n),
+            Value::Bool(b) => write!(fmt, "{}", b),
+            Value::Mem(m) => write!(fmt, "{}", m),
+        }
+    }
+}
+
+/// Folds an operator over the values of its children, if they are known.
+pub fn fold(op: &str, args: &[Option<&Value>]) -> Option<Value> {
+    use Value::*;
+    match (op, args) {
+        ("+", [Some(Int(a)), Some(Int(b))]) => Some(Int(a.checked_add(*b)?)),
+        ("-", [Some(Int(a)), Some(Int(b))]) => Some(Int(a.checked_sub(*b)?)),
+        ("*", [Some(Int(a)), Some(Int(b))]) => Some(Int(a.checked_mul(*b)?)),
+        ("<", [Some(Int(a)), Some(Int(b))]) => Some(Bool(a < b)),
+        ("||", [Some(Bool(a)), Some(Bool(b))]) => Some(Bool(*a || *b)),
+        ("&&", [Some(Bool(a)), Some(Bool(b))]) => Some(Bool(*a && *b)),
+        ("!!", [Some(Bool(a))]) => Some(Bool(!a)),
+        _ => None,
+    }
+}
+
+/// Adds the node holding `value` to the e-graph. After the analysis has
+/// found a value for a class, this puts the constant into the class so it can
+/// be extracted.
+pub fn add_value<L, N>(egraph: &mut EGraph<L, N>, value: &Value) -> Id
+where
+    L: Language + FromOp,
+    N: Analysis<L>,
+{
+    egraph.add(L::from_op(&value.to_string(), vec![]).unwrap())
+}
+
+pub fn merge_value(to: &mut Option<Value>, from: Option<Value>) -> DidMerge {
+    merge_option(to, from, |a, b| {
+        debug_assert_eq!(*a, b, "merged e-classes with different values");
+        DidMerge(false, false)
+    })
+}
+
+fn value<L, N>(egraph: &EGraph<L, N>, id: Id) -> Option<&Value>
+where
+    L: Language,
+    N: Analysis<L, Data = Option<Value>>,
+{
+    egraph[id].data.as_ref()
+}
+
+fn memory<L, N>(egraph: &EGraph<L, N>, id: Id) -> Option<Memory>
+where
+    L: Language,
+    N: Analysis<L, Data = Option<Value>>,
+{
+    match value(egraph, id) {
+        Some(Value::Mem(m)) => Some(m.clone()),
+        _ => None,
+    }
+}
+
+// The name of a program variable in the class, if there is one.
+fn variable<L, N>(egraph: &EGraph<L, N>, id: Id) -> Option<String>
+where
+    L: Language + fmt::Display,
+    N: Analysis<L, Data = Option<Value>>,
+{
+    egraph[id].nodes.iter().filter(|n| n.is_leaf()).map(|n| n.to_string()).find(|name| {
+        name.starts_with(|c: char| c.is_alphabetic()) && !matches!(name.as_str(), "true" | "false" | "skip")
+    })
+}
+
+fn add_eval<L, N>(egraph: &mut EGraph<L, N>, program: Id, mem: Id) -> Id
+where
+    L: Language + FromOp,
+    N: Analysis<L>,
+{
+    egraph.add(L::from_op("eval", vec![program, mem]).unwrap())
+}
+
+fn union_with<L: Language, N: Analysis<L>>(egraph: &mut EGraph<L, N>, eclass: Id, id: Id) -> Vec<Id> {
+    if egraph.union(eclass, id) {
+        vec![eclass]
+    } else {
+        vec![]
+    }
+}
+
+/// `(a::Union{Bool,Int}, σ::Mem) => a`
+pub fn is_literal<L, N>(var: &str) -> impl Fn(&mut EGraph<L, N>, Id, &Subst) -> bool
+where
+    L: Language,
+    N: Analysis<L, Data = Option<Value>>,
+{
+    let var: Var = var.parse().unwrap();
+    move |egraph, _, subst| matches!(value(egraph, subst[var]), Some(Value::Int(_) | Value::Bool(_)))
+}
+
+/// `(v::Symbol, σ::Mem) => σ[v]`
+pub struct ReadMem {
+    pub var: Var,
+    pub mem: Var,
+}
+
+impl<L, N> Applier<L, N> for ReadMem
+where
+    L: Language + FromOp + fmt::Display,
+    N: Analysis<L, Data = Option<Value>>,
+{
+    fn apply_one(&self, egraph: &mut EGraph<L, N>, eclass: Id, subst: &Subst, _: Option<&PatternAst<L>>, _: Symbol) -> Vec<Id> {
+        let read = variable(egraph, subst[self.var])
+            .zip(memory(egraph, subst[self.mem]))
+            .and_then(|(var, mem)| mem.get(&var));
+        match read {
+            Some(n) => {
+                let id = add_value(egraph, &Value::Int(n));
+                union_with(egraph, eclass, id)
+            }
+            None => vec![],
+        }
+    }
+}
+
+/// `(sym::Symbol = val, σ::Mem) => σ[sym] = (val, σ)`
+///
+/// The value of `(eval val σ)` is added to the e-graph and the memory is
+/// written once a later iteration has computed it.
+pub struct WriteMem {
+    pub var: Var,
+    pub expr: Var,
+    pub mem: Var,
+}
+
+impl<L, N> Applier<L, N> for WriteMem
+where
+    L: Language + FromOp + fmt::Display,
+    N: Analysis<L, Data = Option<Value>>,
+{
+    fn apply_one(&self, egraph: &mut EGraph<L, N>, eclass: Id, subst: &Subst, _: Option<&PatternAst<L>>, _: Symbol) -> Vec<Id> {
+        let (var, mem) = match (variable(egraph, subst[self.var]), memory(egraph, subst[self.mem])) {
+            (Some(var), Some(mem)) => (var, mem),
+            _ => return vec![],
+        };
+        let evaluated = add_eval(egraph, subst[self.expr], subst[self.mem]);
+        match value(egraph, evaluated) {
+            Some(Value::Int(n)) => {
+                let id = add_value(egraph, &Value::Mem(mem.set(&var, *n)));
+                union_with(egraph, eclass, id)
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// `(if guard::Bool; t; else; f; end, σ::Mem) => guard ? (t, σ) : (f, σ)`
+///
+/// Only the branch that is taken is evaluated, otherwise unrolling a `while`
+/// would never terminate.
+pub struct EvalIf {
+    pub guard: Var,
+    pub then: Var,
+    pub otherwise: Var,
+    pub mem: Var,
+}
+
+impl<L, N> Applier<L, N> for EvalIf
+where
+    L: Language + FromOp + fmt::Display,
+    N: Analysis<L, Data = Option<Value>>,
+{
+    fn apply_one(&self, egraph: &mut EGraph<L, N>, eclass: Id, subst: &Subst, _: Option<&PatternAst<L>>, _: Symbol) -> Vec<Id> {
+        if memory(egraph, subst[self.mem]).is_none() {
+            return vec![];
+        }
+        let guard = add_eval(egraph, subst[self.guard], subst[self.mem]);
+        let branch = match value(egraph, guard) {
+            Some(Value::Bool(true)) => self.then,
+            Some(Value::Bool(false)) => self.otherwise,
+            _ => return vec![],
+        };
+        let id = add_eval(egraph, subst[branch], subst[self.mem]);
+        union_with(egraph, eclass, id)
+    }
+}
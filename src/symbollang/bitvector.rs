@@ -0,0 +1,90 @@
+use crate::bitvector::*;
+use egg::*;
+
+#[derive(Default, Clone)]
+pub struct ConstantFold;
+
+impl Analysis<SymbolLang> for ConstantFold {
+    type Data = Option<Word>;
+
+    fn make(egraph: &EGraph<SymbolLang, Self>, enode: &SymbolLang) -> Self::Data {
+        let op = enode.op.as_str();
+        if enode.children.is_empty() {
+            op.parse().ok()
+        } else {
+            let args: Vec<Option<Word>> = enode.children.iter().map(|i| egraph[*i].data).collect();
+            fold(op, &args)
+        }
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_constant(to, from)
+    }
+
+    fn modify(egraph: &mut EGraph<SymbolLang, Self>, id: Id) {
+        add_constant(egraph, id)
+    }
+}
+
+pub fn bitvector_rules() -> Vec<Rewrite<SymbolLang, ConstantFold>> {
+    [
+        // commutativity and associativity
+        vec![rewrite!("add-comm"; "(+ ?a ?b)" => "(+ ?b ?a)")],
+        vec![rewrite!("mul-comm"; "(* ?a ?b)" => "(* ?b ?a)")],
+        vec![rewrite!("and-comm"; "(& ?a ?b)" => "(& ?b ?a)")],
+        vec![rewrite!("or-comm"; "(| ?a ?b)" => "(| ?b ?a)")],
+        vec![rewrite!("xor-comm"; "(^ ?a ?b)" => "(^ ?b ?a)")],
+        vec![rewrite!("add-assoc"; "(+ ?a (+ ?b ?c))" => "(+ (+ ?a ?b) ?c)")],
+        vec![rewrite!("mul-assoc"; "(* ?a (* ?b ?c))" => "(* (* ?a ?b) ?c)")],
+        vec![rewrite!("and-assoc"; "(& ?a (& ?b ?c))" => "(& (& ?a ?b) ?c)")],
+        vec![rewrite!("or-assoc"; "(| ?a (| ?b ?c))" => "(| (| ?a ?b) ?c)")],
+        vec![rewrite!("xor-assoc"; "(^ ?a (^ ?b ?c))" => "(^ (^ ?a ?b) ?c)")],
+
+        // identities and annihilators
+        vec![rewrite!("add-zero"; "(+ ?a 0)" => "?a")],
+        vec![rewrite!("sub-zero"; "(- ?a 0)" => "?a")],
+        vec![rewrite!("sub-self"; "(- ?a ?a)" => "0")],
+        vec![rewrite!("mul-one"; "(* ?a 1)" => "?a")],
+        vec![rewrite!("mul-zero"; "(* ?a 0)" => "0")],
+        vec![rewrite!("and-ones"; "(& ?a 255)" => "?a")],
+        vec![rewrite!("and-zero"; "(& ?a 0)" => "0")],
+        vec![rewrite!("and-self"; "(& ?a ?a)" => "?a")],
+        vec![rewrite!("or-zero"; "(| ?a 0)" => "?a")],
+        vec![rewrite!("or-ones"; "(| ?a 255)" => "255")],
+        vec![rewrite!("or-self"; "(| ?a ?a)" => "?a")],
+        vec![rewrite!("xor-zero"; "(^ ?a 0)" => "?a")],
+        vec![rewrite!("xor-self"; "(^ ?a ?a)" => "0")],
+        vec![rewrite!("shl-zero"; "(<< ?a 0)" => "?a")],
+        vec![rewrite!("shr-zero"; "(>> ?a 0)" => "?a")],
+        vec![rewrite!("zero-shl"; "(<< 0 ?a)" => "0")],
+        vec![rewrite!("zero-shr"; "(>> 0 ?a)" => "0")],
+
+        // negation and complement, in two's complement
+        vec![rewrite!("sub-neg"; "(- ?a ?b)" => "(+ ?a (* ?b 255))")],
+        vec![rewrite!("mul-ones"; "(* ?a 255)" => "(- 0 ?a)")],
+        vec![rewrite!("not-not"; "(~ (~ ?a))" => "?a")],
+        vec![rewrite!("xor-ones"; "(^ ?a 255)" => "(~ ?a)")],
+        vec![rewrite!("not-plus-one"; "(+ (~ ?a) 1)" => "(- 0 ?a)")],
+        vec![rewrite!("add-not"; "(+ ?a (~ ?a))" => "255")],
+        rewrite!("demorgan-and"; "(~ (& ?a ?b))" <=> "(| (~ ?a) (~ ?b))"),
+        rewrite!("demorgan-or"; "(~ (| ?a ?b))" <=> "(& (~ ?a) (~ ?b))"),
+
+        // distributivity
+        rewrite!("mul-add-distr"; "(* ?a (+ ?b ?c))" <=> "(+ (* ?a ?b) (* ?a ?c))"),
+        rewrite!("and-or-distr"; "(& ?a (| ?b ?c))" <=> "(| (& ?a ?b) (& ?a ?c))"),
+        rewrite!("and-xor-distr"; "(& ?a (^ ?b ?c))" <=> "(^ (& ?a ?b) (& ?a ?c))"),
+        vec![rewrite!("shl-add-distr"; "(<< (+ ?a ?b) ?c)" => "(+ (<< ?a ?c) (<< ?b ?c))")],
+        vec![rewrite!("mul-shl"; "(* (<< ?a ?b) ?c)" => "(<< (* ?a ?c) ?b)")],
+
+        // strength reduction
+        vec![rewrite!("add-self"; "(+ ?a ?a)" => "(<< ?a 1)")],
+        vec![rewrite!("shl-mul"; "(<< ?a ?b)" => "(* ?a (<< 1 ?b))")],
+        mul_pow2_rules(),
+
+        // mixed boolean-arithmetic identities
+        vec![rewrite!("mba-xor-and"; "(+ (^ ?a ?b) (<< (& ?a ?b) 1))" => "(+ ?a ?b)")],
+        vec![rewrite!("mba-or-and"; "(+ (| ?a ?b) (& ?a ?b))" => "(+ ?a ?b)")],
+        vec![rewrite!("mba-or-sub-and"; "(- (| ?a ?b) (& ?a ?b))" => "(^ ?a ?b)")],
+    ]
+    .concat()
+}
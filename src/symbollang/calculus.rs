@@ -0,0 +1,68 @@
+use crate::calculus::*;
+use crate::eval::Rational;
+use egg::*;
+
+#[derive(Default)]
+pub struct ConstantFold;
+
+impl Analysis<SymbolLang> for ConstantFold {
+    type Data = Option<Rational>;
+
+    fn make(egraph: &EGraph<SymbolLang, Self>, enode: &SymbolLang) -> Self::Data {
+        let op = enode.op.as_str();
+        if enode.children.is_empty() {
+            op.parse().ok()
+        } else {
+            let args: Vec<Option<Rational>> = enode.children.iter().map(|i| egraph[*i].data).collect();
+            fold(op, &args)
+        }
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_constant(to, from)
+    }
+
+    fn modify(egraph: &mut EGraph<SymbolLang, Self>, id: Id) {
+        add_constant(egraph, id)
+    }
+}
+
+pub fn calculus_rules() -> Vec<Rewrite<SymbolLang, ConstantFold>> {
+    vec![
+        // arithmetic
+        rewrite!("comm-add"; "(+ ?a ?b)" => "(+ ?b ?a)"),
+        rewrite!("comm-mul"; "(* ?a ?b)" => "(* ?b ?a)"),
+        rewrite!("assoc-add"; "(+ ?a (+ ?b ?c))" => "(+ (+ ?a ?b) ?c)"),
+        rewrite!("assoc-mul"; "(* ?a (* ?b ?c))" => "(* (* ?a ?b) ?c)"),
+        rewrite!("sub-canon"; "(- ?a ?b)" => "(+ ?a (* -1 ?b))"),
+        rewrite!("div-canon"; "(/ ?a ?b)" => "(* ?a (pow ?b -1))" if is_not_zero("?b")),
+        rewrite!("zero-add"; "(+ ?a 0)" => "?a"),
+        rewrite!("zero-mul"; "(* ?a 0)" => "0"),
+        rewrite!("one-mul"; "(* ?a 1)" => "?a"),
+        rewrite!("cancel-sub"; "(- ?a ?a)" => "0"),
+        rewrite!("cancel-div"; "(/ ?a ?a)" => "1" if is_not_zero("?a")),
+        rewrite!("distribute"; "(* ?a (+ ?b ?c))" => "(+ (* ?a ?b) (* ?a ?c))"),
+        rewrite!("factor"; "(+ (* ?a ?b) (* ?a ?c))" => "(* ?a (+ ?b ?c))"),
+        rewrite!("pow-mul"; "(* (pow ?a ?b) (pow ?a ?c))" => "(pow ?a (+ ?b ?c))"),
+        rewrite!("pow0"; "(pow ?x 0)" => "1" if is_not_zero("?x")),
+        rewrite!("pow1"; "(pow ?x 1)" => "?x"),
+        rewrite!("pow2"; "(pow ?x 2)" => "(* ?x ?x)"),
+        rewrite!("pow-recip"; "(pow ?x -1)" => "(/ 1 ?x)" if is_not_zero("?x")),
+        rewrite!("recip-mul-div"; "(* ?x (/ 1 ?x))" => "1" if is_not_zero("?x")),
+
+        // differentiation, with the chain rule for sin, cos and ln
+        rewrite!("d-variable"; "(d ?x ?x)" => "1" if is_sym("?x")),
+        rewrite!("d-constant"; "(d ?x ?c)" => "0" if is_sym("?x") if is_const_or_distinct_var("?c", "?x")),
+        rewrite!("d-add"; "(d ?x (+ ?a ?b))" => "(+ (d ?x ?a) (d ?x ?b))"),
+        rewrite!("d-mul"; "(d ?x (* ?a ?b))" => "(+ (* ?a (d ?x ?b)) (* ?b (d ?x ?a)))"),
+        rewrite!("d-sin"; "(d ?x (sin ?f))" => "(* (cos ?f) (d ?x ?f))"),
+        rewrite!("d-cos"; "(d ?x (cos ?f))" => "(* (* -1 (sin ?f)) (d ?x ?f))"),
+        rewrite!("d-ln"; "(d ?x (ln ?f))" => "(* (/ 1 ?f) (d ?x ?f))" if is_not_zero("?f")),
+        rewrite!("d-power";
+            "(d ?x (pow ?f ?g))" =>
+            "(* (pow ?f ?g) (+ (* (d ?x ?f) (/ ?g ?f)) (* (d ?x ?g) (ln ?f))))"
+            if is_not_zero("?f")
+            if is_not_zero("?g")
+        ),
+    ]
+}
@@ -0,0 +1,53 @@
+use egg::*;
+
+pub fn circuit_rules() -> Vec<Rewrite<SymbolLang, ()>> {
+    vec![
+        // commutativity and associativity
+        vec![rewrite!("and-comm"; "(and ?a ?b)" => "(and ?b ?a)")],
+        vec![rewrite!("or-comm"; "(or ?a ?b)" => "(or ?b ?a)")],
+        vec![rewrite!("xor-comm"; "(xor ?a ?b)" => "(xor ?b ?a)")],
+        rewrite!("and-assoc"; "(and (and ?a ?b) ?c)" <=> "(and ?a (and ?b ?c))"),
+        rewrite!("or-assoc"; "(or (or ?a ?b) ?c)" <=> "(or ?a (or ?b ?c))"),
+        rewrite!("xor-assoc"; "(xor (xor ?a ?b) ?c)" <=> "(xor ?a (xor ?b ?c))"),
+
+        // identities and annihilators
+        vec![rewrite!("and-idem"; "(and ?a ?a)" => "?a")],
+        vec![rewrite!("or-idem"; "(or ?a ?a)" => "?a")],
+        vec![rewrite!("xor-self"; "(xor ?a ?a)" => "false")],
+        vec![rewrite!("and-true"; "(and ?a true)" => "?a")],
+        vec![rewrite!("and-false"; "(and ?a false)" => "false")],
+        vec![rewrite!("or-true"; "(or ?a true)" => "true")],
+        vec![rewrite!("or-false"; "(or ?a false)" => "?a")],
+        vec![rewrite!("xor-false"; "(xor ?a false)" => "?a")],
+        vec![rewrite!("xor-true"; "(xor ?a true)" => "(not ?a)")],
+        vec![rewrite!("not-true"; "(not true)" => "false")],
+        vec![rewrite!("not-false"; "(not false)" => "true")],
+        vec![rewrite!("and-compl"; "(and ?a (not ?a))" => "false")],
+        vec![rewrite!("or-compl"; "(or ?a (not ?a))" => "true")],
+        vec![rewrite!("and-absorb"; "(and ?a (or ?a ?b))" => "?a")],
+        vec![rewrite!("or-absorb"; "(or ?a (and ?a ?b))" => "?a")],
+
+        // negation, only in the directions that remove gates, as the others
+        // keep growing the e-graph without making a circuit smaller
+        vec![rewrite!("not-not"; "(not (not ?a))" => "?a")],
+        vec![rewrite!("demorgan-and"; "(or (not ?a) (not ?b))" => "(not (and ?a ?b))")],
+        vec![rewrite!("demorgan-or"; "(and (not ?a) (not ?b))" => "(not (or ?a ?b))")],
+        vec![rewrite!("not-xor"; "(xor (not ?a) ?b)" => "(not (xor ?a ?b))")],
+
+        // factoring
+        vec![rewrite!("and-or-factor"; "(or (and ?a ?b) (and ?a ?c))" => "(and ?a (or ?b ?c))")],
+        vec![rewrite!("or-and-factor"; "(and (or ?a ?b) (or ?a ?c))" => "(or ?a (and ?b ?c))")],
+        vec![rewrite!("and-xor-factor"; "(xor (and ?a ?b) (and ?a ?c))" => "(and ?a (xor ?b ?c))")],
+
+        // sums of products as xor and mux
+        vec![rewrite!("xor-sop"; "(or (and ?a (not ?b)) (and (not ?a) ?b))" => "(xor ?a ?b)")],
+        vec![rewrite!("mux-sop"; "(or (and ?s ?a) (and (not ?s) ?b))" => "(mux ?s ?a ?b)")],
+        vec![rewrite!("mux-same"; "(mux ?s ?a ?a)" => "?a")],
+        vec![rewrite!("mux-true"; "(mux true ?a ?b)" => "?a")],
+        vec![rewrite!("mux-false"; "(mux false ?a ?b)" => "?b")],
+        vec![rewrite!("mux-select"; "(mux ?s true false)" => "?s")],
+        vec![rewrite!("mux-not"; "(mux ?s false true)" => "(not ?s)")],
+        vec![rewrite!("mux-swap"; "(mux (not ?s) ?a ?b)" => "(mux ?s ?b ?a)")],
+    ]
+    .concat()
+}
@@ -0,0 +1,71 @@
+use crate::lambda::*;
+use egg::*;
+
+fn eval(egraph: &EGraph<SymbolLang, LambdaAnalysis>, enode: &SymbolLang) -> Option<SymbolLang> {
+    let x = |i: &Id| egraph[*i].data.constant.as_ref();
+    let num = |c: &SymbolLang| c.op.as_str().parse::<i32>().ok();
+    match (enode.op.as_str(), enode.children.as_slice()) {
+        (op, []) if op.parse::<i32>().is_ok() || op.parse::<bool>().is_ok() => Some(enode.clone()),
+        ("+", [a, b]) => Some(SymbolLang::leaf(num(x(a)?)?.checked_add(num(x(b)?)?)?.to_string())),
+        ("=", [a, b]) => Some(SymbolLang::leaf((x(a)? == x(b)?).to_string())),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+pub struct LambdaAnalysis;
+
+impl Analysis<SymbolLang> for LambdaAnalysis {
+    type Data = LambdaData<SymbolLang>;
+
+    fn make(egraph: &EGraph<SymbolLang, Self>, enode: &SymbolLang) -> Self::Data {
+        LambdaData { free: free_vars(egraph, enode), constant: eval(egraph, enode) }
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_data(to, from)
+    }
+
+    fn modify(egraph: &mut EGraph<SymbolLang, Self>, id: Id) {
+        add_constant(egraph, id)
+    }
+}
+
+pub fn lambda_rules() -> Vec<Rewrite<SymbolLang, LambdaAnalysis>> {
+    vec![
+        // open term rules
+        rewrite!("if-true"; "(if true ?then ?else)" => "?then"),
+        rewrite!("if-false"; "(if false ?then ?else)" => "?else"),
+        rewrite!("if-elim"; "(if (= (var ?x) ?e) ?then ?else)" => "?else"
+            if ConditionEqual::parse("(let ?x ?e ?then)", "(let ?x ?e ?else)")),
+        rewrite!("add-comm"; "(+ ?a ?b)" => "(+ ?b ?a)"),
+        rewrite!("add-assoc"; "(+ (+ ?a ?b) ?c)" => "(+ ?a (+ ?b ?c))"),
+        rewrite!("eq-comm"; "(= ?a ?b)" => "(= ?b ?a)"),
+
+        // substitution rules
+        rewrite!("fix"; "(fix ?v ?e)" => "(let ?v (fix ?v ?e) ?e)"),
+        rewrite!("beta"; "(app (lam ?v ?body) ?e)" => "(let ?v ?e ?body)"),
+        rewrite!("let-app"; "(let ?v ?e (app ?a ?b))" => "(app (let ?v ?e ?a) (let ?v ?e ?b))"),
+        rewrite!("let-add"; "(let ?v ?e (+ ?a ?b))" => "(+ (let ?v ?e ?a) (let ?v ?e ?b))"),
+        rewrite!("let-eq"; "(let ?v ?e (= ?a ?b))" => "(= (let ?v ?e ?a) (let ?v ?e ?b))"),
+        rewrite!("let-const"; "(let ?v ?e ?c)" => "?c" if is_const("?c")),
+        rewrite!("let-if";
+            "(let ?v ?e (if ?cond ?then ?else))" =>
+            "(if (let ?v ?e ?cond) (let ?v ?e ?then) (let ?v ?e ?else))"
+        ),
+        rewrite!("let-var-same"; "(let ?v1 ?e (var ?v1))" => "?e"),
+        rewrite!("let-var-diff"; "(let ?v1 ?e (var ?v2))" => "(var ?v2)"
+            if is_not_same_var("?v1", "?v2")),
+        rewrite!("let-lam-same"; "(let ?v1 ?e (lam ?v1 ?body))" => "(lam ?v1 ?body)"),
+        rewrite!("let-lam-diff";
+            "(let ?v1 ?e (lam ?v2 ?body))" =>
+            { CaptureAvoid {
+                fresh: "?fresh".parse().unwrap(),
+                v2: "?v2".parse().unwrap(),
+                e: "?e".parse().unwrap(),
+                if_not_free: "(lam ?v2 (let ?v1 ?e ?body))".parse().unwrap(),
+                if_free: "(lam ?fresh (let ?v1 ?e (let ?v2 (var ?fresh) ?body)))".parse().unwrap(),
+            }}
+            if is_not_same_var("?v1", "?v2")),
+    ]
+}
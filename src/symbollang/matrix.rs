@@ -0,0 +1,41 @@
+use crate::matrix::*;
+use egg::*;
+
+#[derive(Default, Clone)]
+pub struct Dimensions;
+
+impl Analysis<SymbolLang> for Dimensions {
+    type Data = Option<Shape>;
+
+    fn make(egraph: &EGraph<SymbolLang, Self>, enode: &SymbolLang) -> Self::Data {
+        let args: Vec<Option<Shape>> = enode.children.iter().map(|i| egraph[*i].data).collect();
+        shape(enode.op.as_str(), &args)
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_shape(to, from)
+    }
+}
+
+pub fn matrix_rules() -> Vec<Rewrite<SymbolLang, Dimensions>> {
+    [
+        // products and sums
+        rewrite!("mul-assoc"; "(* (* ?a ?b) ?c)" <=> "(* ?a (* ?b ?c))"),
+        vec![rewrite!("add-comm"; "(+ ?a ?b)" => "(+ ?b ?a)")],
+        rewrite!("add-assoc"; "(+ (+ ?a ?b) ?c)" <=> "(+ ?a (+ ?b ?c))"),
+        rewrite!("mul-add-distr"; "(* ?a (+ ?b ?c))" <=> "(+ (* ?a ?b) (* ?a ?c))"),
+        rewrite!("add-mul-distr"; "(* (+ ?a ?b) ?c)" <=> "(+ (* ?a ?c) (* ?b ?c))"),
+
+        // transposes
+        vec![rewrite!("transpose-transpose"; "(transpose (transpose ?a))" => "?a")],
+        rewrite!("transpose-mul"; "(transpose (* ?a ?b))" <=> "(* (transpose ?b) (transpose ?a))"),
+        rewrite!("transpose-add"; "(transpose (+ ?a ?b))" <=> "(+ (transpose ?a) (transpose ?b))"),
+        rewrite!("transpose-scale"; "(transpose (scale ?s ?a))" <=> "(scale ?s (transpose ?a))"),
+
+        // scaling
+        rewrite!("scale-mul-left"; "(* (scale ?s ?a) ?b)" <=> "(scale ?s (* ?a ?b))"),
+        rewrite!("scale-mul-right"; "(* ?a (scale ?s ?b))" <=> "(scale ?s (* ?a ?b))"),
+        rewrite!("scale-add"; "(scale ?s (+ ?a ?b))" <=> "(+ (scale ?s ?a) (scale ?s ?b))"),
+    ]
+    .concat()
+}
@@ -0,0 +1,12 @@
+pub mod basic_maths;
+pub mod bitvector;
+pub mod calc_logic;
+pub mod calculus;
+pub mod circuits;
+pub mod lambda;
+pub mod matrix;
+pub mod prop_logic;
+pub mod reachability;
+pub mod relational;
+pub mod variadic_maths;
+pub mod while_superinterpreter;
@@ -0,0 +1,19 @@
+use crate::reachability::*;
+use egg::*;
+
+pub fn reachability_rules() -> Vec<Rewrite<SymbolLang, ()>> {
+    vec![
+        // edge(x, y) --> path(x, y)
+        rewrite!("path-base"; "(edge ?x ?y)" => "(path ?x ?y)"),
+        // edge(x, y), path(y, z) --> path(x, z)
+        multi_rewrite!("path-trans"; "?t = (edge ?x ?y), ?t = (path ?y ?z)" => "?t = (path ?x ?z)"),
+    ]
+}
+
+// the same rules, with the join of path-trans done by the applier
+pub fn reachability_join_rules() -> Vec<Rewrite<SymbolLang, ()>> {
+    vec![
+        rewrite!("path-base"; "(edge ?x ?y)" => "(path ?x ?y)"),
+        rewrite!("path-trans"; "(edge ?x ?y)" => { JoinPaths::new("?x", "?y") }),
+    ]
+}
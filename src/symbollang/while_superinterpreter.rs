@@ -0,0 +1,92 @@
+use crate::superinterpreter::*;
+use egg::*;
+
+#[derive(Default)]
+pub struct WhileAnalysis;
+
+impl Analysis<SymbolLang> for WhileAnalysis {
+    type Data = Option<Value>;
+
+    fn make(egraph: &EGraph<SymbolLang, Self>, enode: &SymbolLang) -> Self::Data {
+        let op = enode.op.as_str();
+        if enode.children.is_empty() {
+            if let Ok(b) = op.parse() {
+                Some(Value::Bool(b))
+            } else if let Ok(n) = op.parse() {
+                Some(Value::Int(n))
+            } else {
+                op.parse().ok().map(Value::Mem)
+            }
+        } else {
+            let args: Vec<Option<&Value>> = enode.children.iter().map(|i| egraph[*i].data.as_ref()).collect();
+            fold(op, &args)
+        }
+    }
+
+    fn merge(&mut self, to: &mut Self::Data, from: Self::Data) -> DidMerge {
+        merge_value(to, from)
+    }
+
+    fn modify(egraph: &mut EGraph<SymbolLang, Self>, id: Id) {
+        if let Some(value) = egraph[id].data.clone() {
+            let added = add_value(egraph, &value);
+            egraph.union(id, added);
+        }
+    }
+}
+
+pub fn while_rules() -> Vec<Rewrite<SymbolLang, WhileAnalysis>> {
+    vec![
+        // read_mem
+        // (v::Symbol, σ::Mem) => σ[v]
+        rewrite!("read-mem"; "(eval ?v ?mem)" => { ReadMem { var: "?v".parse().unwrap(), mem: "?mem".parse().unwrap() } }),
+
+        // arithm_rules
+        // (a + b, σ::Mem) --> (a, σ) + (b, σ)
+        rewrite!("eval-add"; "(eval (+ ?a ?b) ?mem)" => "(+ (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a * b, σ::Mem) --> (a, σ) * (b, σ)
+        rewrite!("eval-mul"; "(eval (* ?a ?b) ?mem)" => "(* (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a - b, σ::Mem) --> (a, σ) - (b, σ)
+        rewrite!("eval-sub"; "(eval (- ?a ?b) ?mem)" => "(- (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a::Union{Bool,Int}, σ::Mem) => a
+        rewrite!("eval-literal"; "(eval ?a ?mem)" => "?a" if is_literal("?a")),
+
+        // bool_rules
+        // (a < b, σ::Mem) --> (a, σ) < (b, σ)
+        rewrite!("eval-lt"; "(eval (< ?a ?b) ?mem)" => "(< (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a || b, σ::Mem) --> (a, σ) || (b, σ)
+        rewrite!("eval-or"; "(eval (|| ?a ?b) ?mem)" => "(|| (eval ?a ?mem) (eval ?b ?mem))"),
+        // (a && b, σ::Mem) --> (a, σ) && (b, σ)
+        rewrite!("eval-and"; "(eval (&& ?a ?b) ?mem)" => "(&& (eval ?a ?mem) (eval ?b ?mem))"),
+        // (!(a), σ::Mem) --> !((a, σ))
+        rewrite!("eval-not"; "(eval (!! ?a) ?mem)" => "(!! (eval ?a ?mem))"),
+
+        // if_rules
+        // (if guard::Bool; t; else; f; end, σ::Mem) => guard ? (t, σ) : (f, σ)
+        rewrite!("eval-if"; "(eval (if ?guard ?t ?f) ?mem)" => {
+            EvalIf {
+                guard: "?guard".parse().unwrap(),
+                then: "?t".parse().unwrap(),
+                otherwise: "?f".parse().unwrap(),
+                mem: "?mem".parse().unwrap(),
+            }
+        }),
+
+        // while_rules
+        // (:skip, σ::Mem) --> σ
+        rewrite!("eval-skip"; "(eval skip ?mem)" => "?mem"),
+        // (while guard; body; end, σ::Mem) --> (if guard; body; while guard; body; end; end, σ)
+        rewrite!("eval-while"; "(eval (while ?guard ?body) ?mem)"
+            => "(eval (if ?guard (seq ?body (while ?guard ?body)) skip) ?mem)"),
+
+        // seq_rules
+        // ((a; b), σ::Mem) --> (b, (a, σ))
+        rewrite!("eval-seq"; "(eval (seq ?a ?b) ?mem)" => "(eval ?b (eval ?a ?mem))"),
+
+        // write_mem
+        // (sym::Symbol = val, σ::Mem) => (σ[sym] = (val, σ); σ)
+        rewrite!("write-mem"; "(eval (= ?x ?e) ?mem)" => {
+            WriteMem { var: "?x".parse().unwrap(), expr: "?e".parse().unwrap(), mem: "?mem".parse().unwrap() }
+        }),
+    ]
+}
@@ -0,0 +1,82 @@
+// Parameterised families of propositional tautologies, to see how far the
+// `prop_logic` rules scale beyond the three hand-picked goals. Every family is
+// a tautology for each `n >= 1` and grows with `n`.
+
+use crate::prove;
+use egg::*;
+
+// Nests `op` to the right over `items`.
+fn chain(op: &str, items: Vec<String>) -> String {
+    let mut items = items.into_iter().rev();
+    let last = items.next().expect("empty chain");
+    items.fold(last, |acc, item| format!("({} {} {})", op, item, acc))
+}
+
+/// `(p => (q1 => ... => (qn => r))) => ((p => q1) => ... => ((p => qn) => (p => r)))`,
+/// Frege's theorem with `n` middle premises. `frege_chain(1)` is the theorem
+/// of the `prop_logic/freges_theorem` benchmark, up to the names of `q`.
+pub fn frege_chain(n: usize) -> String {
+    assert!(n > 0);
+    let qs: Vec<String> = (1..=n).map(|i| format!("q{}", i)).collect();
+    let premise = chain("=>", ["p".to_string()].into_iter().chain(qs.iter().cloned()).chain(["r".to_string()]).collect());
+    let conclusion = chain(
+        "=>",
+        qs.iter().map(|q| format!("(=> p {})", q)).chain(["(=> p r)".to_string()]).collect(),
+    );
+    format!("(=> {} {})", premise, conclusion)
+}
+
+/// `(p && (q1 || ... || qn)) == ((p && q1) || ... || (p && qn))`, which takes
+/// `n - 1` distributivity steps to prove.
+pub fn distributivity_tower(n: usize) -> String {
+    assert!(n > 0);
+    let qs = chain("||", (1..=n).map(|i| format!("q{}", i)).collect());
+    let products = chain("||", (1..=n).map(|i| format!("(&& p q{})", i)).collect());
+    format!("(== (&& p {}) {})", qs, products)
+}
+
+/// The pigeonhole principle for `n + 1` pigeons and `n` holes: if every pigeon
+/// `i` sits in some hole `j` (variable `p{i}h{j}`), some hole holds two
+/// pigeons. The term has `n (n + 1)` variables, so only small `n` are of use.
+pub fn pigeonhole(n: usize) -> String {
+    assert!(n > 0);
+    let var = |i: usize, j: usize| format!("p{}h{}", i, j);
+    let placed = chain("&&", (0..=n).map(|i| chain("||", (0..n).map(|j| var(i, j)).collect())).collect());
+    let mut collisions = Vec::new();
+    for j in 0..n {
+        for i in 0..=n {
+            for k in i + 1..=n {
+                collisions.push(format!("(&& {} {})", var(i, j), var(k, j)));
+            }
+        }
+    }
+    format!("(=> {} {})", placed, chain("||", collisions))
+}
+
+/// `((x0 => x1) && ... && (x{n-1} => xn)) => (x0 => xn)`, hypothetical
+/// syllogism over a chain of `n` implications.
+pub fn syllogism_chain(n: usize) -> String {
+    assert!(n > 0);
+    let steps = chain("&&", (0..n).map(|i| format!("(=> x{} x{})", i, i + 1)).collect());
+    format!("(=> {} (=> x0 x{}))", steps, n)
+}
+
+/// The largest `n <= max_n` such that [`prove`] reduces `family(1)` up to
+/// `family(n)` to `true` with the given limits, or 0 if it fails on
+/// `family(1)`.
+pub fn largest_proved<L: Language + FromOp>(
+    family: fn(usize) -> String,
+    rules: &Vec<Rewrite<L, ()>>,
+    steps: usize,
+    timeout: usize,
+    max_n: usize,
+) -> usize {
+    let tru: RecExpr<L> = "true".parse().unwrap();
+    (1..=max_n)
+        .take_while(|n| {
+            let goal: RecExpr<L> = family(*n).parse().unwrap();
+            prove(&goal, rules, steps, timeout, &tru).0 == tru
+        })
+        .last()
+        .unwrap_or(0)
+}
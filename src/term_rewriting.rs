@@ -0,0 +1,135 @@
+// Classic (non-saturating) term rewriting over a `RecExpr`, as done by
+// Metatheory.jl's `Fixpoint(Postwalk(Chain(rules)))` strategies. Subterms are
+// normalised bottom-up and the first rule that matches a node replaces it.
+
+use egg::*;
+use std::collections::{HashMap, HashSet};
+
+// Hash-consed terms, so that non-linear patterns can compare subterms by id.
+struct Terms<L> {
+    nodes: Vec<L>,
+    memo: HashMap<L, Id>,
+}
+
+impl<L: Language> Terms<L> {
+    fn add(&mut self, node: L) -> Id {
+        if let Some(id) = self.memo.get(&node) {
+            return *id;
+        }
+        let id = Id::from(self.nodes.len());
+        self.nodes.push(node.clone());
+        self.memo.insert(node, id);
+        id
+    }
+
+    fn add_expr(&mut self, expr: &RecExpr<L>) -> Id {
+        let mut ids: Vec<Id> = vec![];
+        for node in expr.as_ref() {
+            ids.push(self.add(node.clone().map_children(|c| ids[usize::from(c)])));
+        }
+        ids[ids.len() - 1]
+    }
+
+    // Writes the term out as a tree, unsharing common subterms like `parse` does.
+    fn to_expr(&self, id: Id, expr: &mut RecExpr<L>) -> Id {
+        let node = self.nodes[usize::from(id)].clone().map_children(|c| self.to_expr(c, expr));
+        expr.add(node)
+    }
+
+    fn matches(&self, pattern: &PatternAst<L>, pid: Id, id: Id, subst: &mut HashMap<Var, Id>) -> bool {
+        match &pattern[pid] {
+            ENodeOrVar::Var(v) => *subst.entry(*v).or_insert(id) == id,
+            ENodeOrVar::ENode(p) => {
+                let node = &self.nodes[usize::from(id)];
+                p.matches(node)
+                    && p.children()
+                        .iter()
+                        .zip(node.children())
+                        .all(|(pc, c)| self.matches(pattern, *pc, *c, subst))
+            }
+        }
+    }
+
+    fn instantiate(&mut self, pattern: &PatternAst<L>, pid: Id, subst: &HashMap<Var, Id>) -> Id {
+        match &pattern[pid] {
+            ENodeOrVar::Var(v) => subst[v],
+            ENodeOrVar::ENode(p) => {
+                let node = p.clone().map_children(|c| self.instantiate(pattern, c, subst));
+                self.add(node)
+            }
+        }
+    }
+}
+
+struct Rewriter<'a, L> {
+    terms: Terms<L>,
+    rules: Vec<(&'a PatternAst<L>, &'a PatternAst<L>)>,
+    normal_forms: HashMap<Id, Id>,
+    steps: usize,
+    max_steps: usize,
+}
+
+impl<L: Language> Rewriter<'_, L> {
+    fn normalize(&mut self, id: Id) -> Id {
+        if let Some(normal) = self.normal_forms.get(&id) {
+            return *normal;
+        }
+        let node = self.terms.nodes[usize::from(id)].clone().map_children(|c| self.normalize(c));
+        let current = self.terms.add(node);
+
+        let mut normal = current;
+        if self.steps < self.max_steps {
+            for i in 0..self.rules.len() {
+                let (lhs, rhs) = self.rules[i];
+                let mut subst = HashMap::default();
+                if self.terms.matches(lhs, Id::from(lhs.as_ref().len() - 1), current, &mut subst) {
+                    self.steps += 1;
+                    let rewritten = self.terms.instantiate(rhs, Id::from(rhs.as_ref().len() - 1), &subst);
+                    normal = self.normalize(rewritten);
+                    break;
+                }
+            }
+        }
+        self.normal_forms.insert(id, normal);
+        self.normal_forms.insert(current, normal);
+        normal
+    }
+}
+
+/// Rewrites `expr` to a normal form with the directed rules of `rules`,
+/// performing at most `max_steps` rewrites. Returns the result and the number
+/// of rewrites performed.
+///
+/// Equational rules, i.e. the pairs `name` and `name-rev` created by `<=>`,
+/// are skipped: applied in both directions they never reach a fixpoint.
+/// Rules without pattern appliers are skipped as well.
+pub fn rewrite_fixpoint<L: Language, N: Analysis<L>>(
+    expr: &RecExpr<L>,
+    rules: &[Rewrite<L, N>],
+    max_steps: usize,
+) -> (RecExpr<L>, usize) {
+    let names: HashSet<String> = rules.iter().map(|r| r.name.to_string()).collect();
+    let directed = rules
+        .iter()
+        .filter(|r| {
+            let name = r.name.as_str();
+            let reverse = name.strip_suffix("-rev").is_some_and(|n| names.contains(n));
+            !reverse && !names.contains(&format!("{}-rev", name))
+        })
+        .filter_map(|r| Some((r.searcher.get_pattern_ast()?, r.applier.get_pattern_ast()?)))
+        .collect();
+
+    let mut rewriter = Rewriter {
+        terms: Terms { nodes: vec![], memo: HashMap::default() },
+        rules: directed,
+        normal_forms: HashMap::default(),
+        steps: 0,
+        max_steps,
+    };
+    let root = rewriter.terms.add_expr(expr);
+    let normal = rewriter.normalize(root);
+
+    let mut out = RecExpr::default();
+    rewriter.terms.to_expr(normal, &mut out);
+    (out, rewriter.steps)
+}
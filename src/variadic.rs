@@ -0,0 +1,146 @@
+// Shared pieces of the variadic encoding of `BasicMath`, where `+` and `*` take
+// any number of children, like Julia's n-ary calls: `a + b + c` is
+// `(+ a b c)` rather than `(+ a (+ b c))`. Patterns have a fixed arity, so
+// associativity, commutativity and the units are written out for every arity
+// up to `MAX_ARITY`.
+
+use egg::*;
+use std::fmt::Display;
+
+/// The operators that are flattened.
+pub const VARIADIC_OPS: [&str; 2] = ["+", "*"];
+
+/// The largest arity the rules match. Flattening stops at this arity.
+pub const MAX_ARITY: usize = 4;
+
+// Adds the children of `id` to `children`, splicing in the children of those
+// with the same variadic operator.
+fn flatten_children<L>(expr: &RecExpr<L>, id: Id, op: &str, flat: &mut RecExpr<L>, children: &mut Vec<Id>)
+where
+    L: Language + Display + FromOp,
+{
+    for c in expr[id].children() {
+        if VARIADIC_OPS.contains(&op) && expr[*c].to_string() == op {
+            flatten_children(expr, *c, op, flat, children);
+        } else {
+            children.push(flatten_at(expr, *c, flat));
+        }
+    }
+}
+
+// Adds a call of `op`, nesting all but the first `MAX_ARITY - 1` children of
+// a variadic call into its last child, so that the rules can match it.
+fn add_chunked<L: Language + FromOp>(op: &str, mut children: Vec<Id>, flat: &mut RecExpr<L>) -> Id {
+    if VARIADIC_OPS.contains(&op) && children.len() > MAX_ARITY {
+        let rest = add_chunked(op, children.split_off(MAX_ARITY - 1), flat);
+        children.push(rest);
+    }
+    flat.add(L::from_op(op, children).unwrap())
+}
+
+fn flatten_at<L: Language + Display + FromOp>(expr: &RecExpr<L>, id: Id, flat: &mut RecExpr<L>) -> Id {
+    let op = expr[id].to_string();
+    let mut children = vec![];
+    flatten_children(expr, id, &op, flat, &mut children);
+    add_chunked(&op, children, flat)
+}
+
+/// Merges nested `+` and `*` into their parents, so that `(+ a (+ b c))`
+/// becomes `(+ a b c)`. Calls with more than `MAX_ARITY` children are nested
+/// to the right, e.g. `(+ a b c (+ d e))`.
+pub fn flatten<L: Language + Display + FromOp>(expr: &RecExpr<L>) -> RecExpr<L> {
+    let mut flat = RecExpr::default();
+    flatten_at(expr, Id::from(expr.as_ref().len() - 1), &mut flat);
+    flat
+}
+
+fn vars(prefix: &str, n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("?{}{}", prefix, i)).collect()
+}
+
+fn rule<L, N>(name: String, lhs: String, rhs: String) -> Rewrite<L, N>
+where
+    L: Language + FromOp + Display + Send + Sync + 'static,
+    N: Analysis<L>,
+{
+    let lhs: Pattern<L> = lhs.parse().unwrap();
+    let rhs: Pattern<L> = rhs.parse().unwrap();
+    Rewrite::new(name, lhs, rhs).unwrap()
+}
+
+/// Associativity and commutativity of the variadic `op`: flattening a binary
+/// child into its parent, splitting off all but the first child into a nested
+/// call, and swapping neighbouring children. The rules are named after `name`
+/// and the arity, e.g. `commute-add-3-1`.
+pub fn ac_rules<L, N>(name: &str, op: &str) -> Vec<Rewrite<L, N>>
+where
+    L: Language + FromOp + Display + Send + Sync + 'static,
+    N: Analysis<L>,
+{
+    let mut rules = vec![];
+    for n in 2..MAX_ARITY {
+        for i in 0..n {
+            let xs = vars("x", n);
+            let mut lhs = xs.clone();
+            lhs[i] = format!("({} ?y0 ?y1)", op);
+            let mut rhs = xs;
+            rhs.splice(i..=i, vars("y", 2));
+            rules.push(rule(
+                format!("flatten-{}-{}-{}", name, n, i),
+                format!("({} {})", op, lhs.join(" ")),
+                format!("({} {})", op, rhs.join(" ")),
+            ));
+        }
+    }
+    for n in 3..=MAX_ARITY {
+        let xs = vars("x", n);
+        rules.push(rule(
+            format!("split-{}-{}", name, n),
+            format!("({} {})", op, xs.join(" ")),
+            format!("({} {} ({} {}))", op, xs[0], op, xs[1..].join(" ")),
+        ));
+    }
+    for n in 2..=MAX_ARITY {
+        for i in 0..n - 1 {
+            let xs = vars("x", n);
+            let mut swapped = xs.clone();
+            swapped.swap(i, i + 1);
+            rules.push(rule(
+                format!("commute-{}-{}-{}", name, n, i),
+                format!("({} {})", op, xs.join(" ")),
+                format!("({} {})", op, swapped.join(" ")),
+            ));
+        }
+    }
+    rules
+}
+
+/// Drops a trailing `unit` from a call of `op`. Together with the commutativity
+/// rules of [`ac_rules`] this removes units in any position.
+pub fn unit_rules<L, N>(name: &str, op: &str, unit: &str) -> Vec<Rewrite<L, N>>
+where
+    L: Language + FromOp + Display + Send + Sync + 'static,
+    N: Analysis<L>,
+{
+    (2..=MAX_ARITY)
+        .map(|n| {
+            let xs = vars("x", n - 1);
+            let rhs = if n == 2 { xs[0].clone() } else { format!("({} {})", op, xs.join(" ")) };
+            rule(format!("{}-{}-{}", name, unit, n), format!("({} {} {})", op, xs.join(" "), unit), rhs)
+        })
+        .collect()
+}
+
+/// Rewrites a call of `op` with a trailing `zero` to `zero`.
+pub fn absorbing_rules<L, N>(name: &str, op: &str, zero: &str) -> Vec<Rewrite<L, N>>
+where
+    L: Language + FromOp + Display + Send + Sync + 'static,
+    N: Analysis<L>,
+{
+    (2..=MAX_ARITY)
+        .map(|n| {
+            let xs = vars("x", n - 1);
+            rule(format!("{}-absorb-{}", name, n), format!("({} {} {})", op, xs.join(" "), zero), zero.to_string())
+        })
+        .collect()
+}
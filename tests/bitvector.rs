@@ -0,0 +1,54 @@
+use egg::*;
+use egg_benchmark::bitvector::*;
+use egg_benchmark::customlang::bitvector::{bitvector_rules, BitVec, ConstantFold};
+use egg_benchmark::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+
+fn assert_no_counterexample<L: Language + std::fmt::Display, N: Analysis<L>>(rules: &[Rewrite<L, N>]) {
+    let mut rng = StdRng::seed_from_u64(42);
+    for rule in rules {
+        let env = find_counterexample(rule, 1000, &mut rng);
+        assert!(env.is_none(), "{} is unsound under {:?}", rule.name, env);
+    }
+}
+
+#[test]
+fn folding_wraps_around() {
+    assert_eq!(fold("+", &[Some(200), Some(100)]), Some(44));
+    assert_eq!(fold("-", &[Some(0), Some(1)]), Some(255));
+    assert_eq!(fold("*", &[Some(16), Some(17)]), Some(16));
+    assert_eq!(fold("<<", &[Some(1), Some(8)]), Some(0));
+    assert_eq!(fold(">>", &[Some(128), Some(200)]), Some(0));
+    assert_eq!(fold("~", &[Some(0x0f)]), Some(0xf0));
+
+    let expr: RecExpr<BitVec> = "(* (+ 250 10) 3)".parse().unwrap();
+    let (best, _) = simplify(&expr, &bitvector_rules(), 10);
+    assert_eq!(best.to_string(), "12");
+}
+
+#[test]
+fn rules_survive_random_testing() {
+    assert_no_counterexample(&bitvector_rules());
+    assert_no_counterexample(&symbollang::bitvector::bitvector_rules());
+
+    // shifting twice is not shifting by the sum once the sum wraps around
+    let unsound: Rewrite<BitVec, ConstantFold> = rewrite!("shl-shl"; "(<< (<< ?a ?b) ?c)" => "(<< ?a (+ ?b ?c))");
+    let mut rng = StdRng::seed_from_u64(42);
+    assert!(find_counterexample(&unsound, 1000, &mut rng).is_some());
+}
+
+#[test]
+fn generated_families_optimise_to_equivalent_terms() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for src in [mba_sum(5), shift_sum(10), constant_chain(4)] {
+        let expr: RecExpr<BitVec> = src.parse().unwrap();
+        let (best, (before, after), _) = optimize(&expr, &bitvector_rules(), 10, |_| Latency);
+        assert!(after < before, "{} did not improve on {}", best, src);
+        for _ in 0..100 {
+            let env: HashMap<String, Word> = num_vars(&expr).into_iter().map(|v| (v, random_word(&mut rng))).collect();
+            assert_eq!(eval_word(&expr, &env), eval_word(&best, &env), "{} differs from {} under {:?}", best, src, env);
+        }
+    }
+}
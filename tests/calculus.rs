@@ -0,0 +1,52 @@
+use egg::*;
+use egg_benchmark::calculus::fold;
+use egg_benchmark::*;
+use egg_benchmark::{customlang, symbollang};
+
+// The differentiation and simplification goals of egg's math test suite, plus
+// the chain rule.
+const GOALS: &[(&str, &str)] = &[
+    ("(d x x)", "1"),
+    ("(d x y)", "0"),
+    ("(d x (+ 1 (* 2 x)))", "2"),
+    ("(d x (+ 1 (* y x)))", "y"),
+    ("(d x (ln x))", "(/ 1 x)"),
+    ("(d x (pow x 3))", "(* 3 (pow x 2))"),
+    ("(d x (- (pow x 3) (* 7 (pow x 2))))", "(* x (- (* 3 x) 14))"),
+    ("(d x (sin (* 2 x)))", "(* 2 (cos (* 2 x)))"),
+    ("(d x (cos (pow x 2)))", "(* -2 (* x (sin (pow x 2))))"),
+    ("(+ 1 (- a (* (- 2 1) a)))", "1"),
+];
+
+#[test]
+fn proves_goals() {
+    let custom_rules = customlang::calculus::calculus_rules();
+    let symbol_rules = symbollang::calculus::calculus_rules();
+    for (expr, goal) in GOALS {
+        let (equal, _) = equivalent::<customlang::calculus::Math, _>(
+            &expr.parse().unwrap(), &goal.parse().unwrap(), &custom_rules, 30);
+        assert!(equal, "customlang: {} != {}", expr, goal);
+        let (equal, _) = equivalent::<SymbolLang, _>(&expr.parse().unwrap(), &goal.parse().unwrap(), &symbol_rules, 30);
+        assert!(equal, "symbollang: {} != {}", expr, goal);
+    }
+}
+
+#[test]
+fn does_not_prove_wrong_derivative() {
+    let expr: RecExpr<customlang::calculus::Math> = "(d x (pow x 3))".parse().unwrap();
+    let (equal, _) = equivalent(&expr, &"(* 2 (pow x 2))".parse().unwrap(), &customlang::calculus::calculus_rules(), 10);
+    assert!(!equal);
+}
+
+#[test]
+fn folds_constants() {
+    let r = |s: &str| s.parse::<Rational>().unwrap();
+    assert_eq!(fold("/", &[Some(r("6")), Some(r("4"))]), Some(r("3/2")));
+    assert_eq!(fold("pow", &[Some(r("-1/2")), Some(r("-3"))]), Some(r("-8")));
+    assert_eq!(fold("/", &[Some(r("1")), Some(r("0"))]), None);
+    assert_eq!(fold("+", &[Some(r("1")), None]), None);
+
+    let expr: RecExpr<SymbolLang> = "(/ (* 6 x) 4)".parse().unwrap();
+    let (result, _) = simplify(&expr, &symbollang::calculus::calculus_rules(), 10);
+    assert_eq!(result.to_string(), "(* x 3/2)");
+}
@@ -0,0 +1,51 @@
+use egg::*;
+use egg_benchmark::circuits::*;
+use egg_benchmark::customlang::circuits::{circuit_rules, Circuit};
+use egg_benchmark::*;
+
+fn assert_equivalent(a: &str, b: &RecExpr<Circuit>) {
+    let miter: RecExpr<SymbolLang> = format!("(== {} {})", a, b).parse().unwrap();
+    assert!(is_tautology(&miter), "{} is not equivalent to {}", b, a);
+}
+
+#[test]
+fn generated_circuits() {
+    let truth_table = |src: &str| {
+        let expr: RecExpr<SymbolLang> = src.parse().unwrap();
+        let vars = bool_vars(&expr);
+        bool_assignments(&vars).map(|env| eval_bool(&expr, &env).unwrap()).collect::<Vec<bool>>()
+    };
+    // a1 a0 + b1 b0 + cin, bit 1 of the sum
+    let expected: Vec<bool> = (0..32u32)
+        .map(|bits| {
+            // variables in order of first occurrence: a1 b1 a0 b0 cin
+            let bit = |i: u32| (bits >> i) & 1;
+            let a = bit(0) * 2 + bit(2);
+            let b = bit(1) * 2 + bit(3);
+            (a + b + bit(4)) & 2 != 0
+        })
+        .collect();
+    assert_eq!(truth_table(&adder(2)), expected);
+    assert_eq!(truth_table(&parity(3)), [false, true, true, false, true, false, false, true]);
+    // a0 b0, a > b
+    assert_eq!(truth_table(&comparator(1)), [false, true, false, false]);
+}
+
+#[test]
+fn optimization_preserves_function_and_reduces_cost() {
+    for src in [adder(3), comparator(3), parity(5)] {
+        let expr: RecExpr<Circuit> = src.parse().unwrap();
+        for cost in [GateCost::gate_count(), GateCost::area()] {
+            let (best, (before, after), _) = optimize(&expr, &circuit_rules(), 10, |_| cost);
+            assert!(after < before, "{} did not improve on {}", best, src);
+            assert_equivalent(&src, &best);
+        }
+    }
+}
+
+#[test]
+fn parity_becomes_xor_chain() {
+    let expr: RecExpr<Circuit> = parity(8).parse().unwrap();
+    let (_, (_, after), _) = optimize(&expr, &circuit_rules(), 10, |_| GateCost::gate_count());
+    assert_eq!(after, 7);
+}
@@ -0,0 +1,336 @@
+// Runs the benchmark inputs of every theory through its `SymbolLang` and its
+// `define_language!` variant, which are meant to do the same work, and reports
+// where they diverge, which usually means that the two copies of a rule set
+// differ. Every input of the benchmarks and snapshots is run. The e-graphs
+// must stop for the same reason with the same numbers of classes and nodes.
+// The memo is not compared: it keeps stale entries whose number depends on
+// the order in which classes are repaired, which follows the hashes of the
+// nodes. The extracted terms must have the same cost and be represented in
+// the root class of the other e-graph, as ties between terms of equal cost
+// are broken by the order of the nodes, which differs too.
+//
+// The runs use `simplify_runner`, or `prove_runner` for the logic goals, with
+// the iteration limits and steps of the benchmarks, but without their time
+// limit, which makes the debug build stop at different iterations from run to
+// run. Every step of a proof is compared that starts from the same term in
+// both languages: after a step that stops early, the next one may start from
+// a different term of the same size, and then only the results are compared.
+
+use egg::*;
+use egg_benchmark::circuits::GateCost;
+use egg_benchmark::inputs::{self, CALCULUS, DIVISION, LAMBDA, PROP_LOGIC_FAMILIES, WHILE_10};
+use egg_benchmark::matrix::Flops;
+use egg_benchmark::relational::Work;
+use egg_benchmark::*;
+use egg_benchmark::{bitvector, reachability, variadic};
+use std::fmt::{Debug, Display};
+use std::time::Duration;
+
+struct Run<L: Language, N: Analysis<L>> {
+    // the term each runner started from, printed
+    inputs: Vec<String>,
+    runners: Vec<Runner<L, N>>,
+    best: RecExpr<L>,
+    cost: String,
+}
+
+impl<L: Language, N: Analysis<L>> Run<L, N> {
+    fn last(&self) -> &Runner<L, N> {
+        self.runners.last().unwrap()
+    }
+}
+
+fn run<L, N, CF>(expr: &RecExpr<L>, rules: &[Rewrite<L, N>], iter_limit: usize, cost: impl Fn(&EGraph<L, N>) -> CF) -> Run<L, N>
+where
+    L: Language + Display,
+    N: Analysis<L> + Default,
+    CF: CostFunction<L>,
+    CF::Cost: Debug,
+{
+    let runner = simplify_runner(iter_limit)
+        .with_time_limit(Duration::from_secs(3600))
+        .with_expr(expr)
+        .run(rules);
+    let (cost, best) = Extractor::new(&runner.egraph, cost(&runner.egraph)).find_best(runner.roots[0]);
+    Run { inputs: vec![expr.to_string()], runners: vec![runner], best, cost: format!("{:?}", cost) }
+}
+
+fn run_prove<L, N>(expr: &RecExpr<L>, rules: &Vec<Rewrite<L, N>>, steps: usize, iter_limit: usize) -> Run<L, N>
+where
+    L: Language + FromOp + Display,
+    N: Analysis<L> + Default,
+{
+    let tru: RecExpr<L> = "true".parse().unwrap();
+    let (best, runners) =
+        prove_runs(expr, rules, steps, &tru, || prove_runner(iter_limit).with_time_limit(Duration::from_secs(3600)));
+    let mut inputs = vec![expr.to_string()];
+    for runner in &runners[..runners.len() - 1] {
+        inputs.push(Extractor::new(&runner.egraph, AstSize).find_best(runner.roots[0]).1.to_string());
+    }
+    Run { inputs, runners, cost: format!("{:?}", AstSize.cost_rec(&best)), best }
+}
+
+// Whether `term`, printed by the other language, is in the root class of `run`.
+fn represents<L: Language + FromOp, N: Analysis<L>>(run: &Run<L, N>, term: &impl Display) -> bool {
+    let egraph = &run.last().egraph;
+    let expr: RecExpr<L> = term.to_string().parse().unwrap();
+    egraph.lookup_expr(&expr).map(|id| egraph.find(id)) == Some(egraph.find(run.last().roots[0]))
+}
+
+// The node limit is checked against the memo, whose size it reports, so only
+// the kind of the stop reason is compared.
+fn summary<L: Language, N: Analysis<L>>(runner: &Runner<L, N>) -> String {
+    let stop_reason = match runner.stop_reason.as_ref().unwrap() {
+        StopReason::NodeLimit(_) => "NodeLimit".to_string(),
+        reason => format!("{:?}", reason),
+    };
+    format!(
+        "{}, n_classes: {}, n_nodes: {}",
+        stop_reason,
+        runner.egraph.classes().count(),
+        runner.egraph.total_number_of_nodes()
+    )
+}
+
+// For the same reason, the run whose memo holds fewer stale entries may hit
+// the node limit an iteration later. When both do, the iterations both ran
+// are compared instead of the final e-graphs.
+fn summaries<L1, N1, L2, N2>(s: &Runner<L1, N1>, c: &Runner<L2, N2>) -> (String, String)
+where
+    L1: Language,
+    N1: Analysis<L1>,
+    L2: Language,
+    N2: Analysis<L2>,
+{
+    let node_limit = |reason: &Option<StopReason>| matches!(reason, Some(StopReason::NodeLimit(_)));
+    if node_limit(&s.stop_reason) && node_limit(&c.stop_reason) {
+        let n = s.iterations.len().min(c.iterations.len());
+        (iteration_classes(s, n), iteration_classes(c, n))
+    } else {
+        (summary(s), summary(c))
+    }
+}
+
+// The classes after each of the first `n` iterations.
+fn iteration_classes<L: Language, N: Analysis<L>>(runner: &Runner<L, N>, n: usize) -> String {
+    let classes: Vec<String> = runner.iterations[..n].iter().map(|i| i.egraph_classes.to_string()).collect();
+    format!("NodeLimit, n_classes by iteration: {}", classes.join(" "))
+}
+
+fn compare<L1, N1, L2, N2>(diverged: &mut Vec<String>, input: &str, sym: &Run<L1, N1>, cust: &Run<L2, N2>)
+where
+    L1: Language + FromOp + Display,
+    N1: Analysis<L1>,
+    L2: Language + FromOp + Display,
+    N2: Analysis<L2>,
+{
+    for (i, (s, c)) in sym.runners.iter().zip(&cust.runners).enumerate() {
+        // n_memo is left out on purpose, see the top of the file
+        let (s, c) = summaries(s, c);
+        if sym.inputs[i] == cust.inputs[i] && s != c {
+            diverged.push(format!("{} (step {}): {} vs {}", input, i + 1, s, c));
+        }
+    }
+    if sym.cost != cust.cost || !represents(sym, &cust.best) || !represents(cust, &sym.best) {
+        diverged.push(format!("{}: extracted {} ({}) vs {} ({})", input, sym.best, sym.cost, cust.best, cust.cost));
+    }
+}
+
+fn assert_agree(diverged: Vec<String>) {
+    assert!(diverged.is_empty(), "SymbolLang and custom language diverge:\n{}", diverged.join("\n"));
+}
+
+fn parse<L: FromOp>(src: &str) -> RecExpr<L> {
+    src.parse().unwrap()
+}
+
+#[test]
+fn basic_maths() {
+    use customlang::basic_maths as cust;
+    use symbollang::basic_maths as sym;
+    let mut diverged = vec![];
+    for (name, src) in inputs::maths() {
+        let s = run(&parse(&src), &sym::basic_maths_rules(), 8, |_| AstSize);
+        let c = run(&parse::<cust::BasicMath>(&src), &cust::basic_maths_rules(), 8, |_| AstSize);
+        compare(&mut diverged, &format!("basic_maths/{}", name), &s, &c);
+        let s = run(&parse(&src), &sym::basic_maths_sorted_rules(), 8, |_| AstSize);
+        let c = run(&parse::<cust::BasicMath>(&src), &cust::basic_maths_sorted_rules(), 8, |_| AstSize);
+        compare(&mut diverged, &format!("basic_maths_sorted/{}", name), &s, &c);
+    }
+    for (name, src) in DIVISION {
+        let s = run(&parse(src), &sym::basic_maths_division_rules(), 8, |_| AstSize);
+        let c = run(&parse::<cust::DivisionMath>(src), &cust::basic_maths_division_rules(), 8, |_| AstSize);
+        compare(&mut diverged, &format!("basic_maths_division/{}", name), &s, &c);
+        let s = run(&parse(src), &sym::basic_maths_unchecked_division_rules(), 8, |_| AstSize);
+        let c = run(&parse::<cust::DivisionMath>(src), &cust::basic_maths_unchecked_division_rules(), 8, |_| AstSize);
+        compare(&mut diverged, &format!("basic_maths_division_unchecked/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn variadic_maths() {
+    use customlang::variadic_maths as cust;
+    use symbollang::variadic_maths as sym;
+    let mut diverged = vec![];
+    for (name, src) in inputs::maths() {
+        let s = run(&variadic::flatten(&parse(&src)), &sym::variadic_maths_rules(), 8, |_| AstSize);
+        let c = run(&variadic::flatten(&parse::<cust::VariadicMath>(&src)), &cust::variadic_maths_rules(), 8, |_| AstSize);
+        compare(&mut diverged, &format!("variadic_maths/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn prop_logic() {
+    use customlang::prop_logic as cust;
+    use symbollang::prop_logic as sym;
+    let mut diverged = vec![];
+    let mut goals = vec![
+        ("prove1".to_string(), inputs::prove1(), 2, 6),
+        ("demorgan".to_string(), inputs::demorgan(), 1, 10),
+        ("freges_theorem".to_string(), inputs::freges_theorem(), 1, 10),
+    ];
+    for (family, generate, max_n) in PROP_LOGIC_FAMILIES {
+        for n in 1..=max_n {
+            goals.push((format!("{}_{}", family, n), generate(n), 1, 10));
+        }
+    }
+    for (name, src, steps, iter_limit) in &goals {
+        let s = run_prove(&parse(src), &sym::propositional_logic_rules(), *steps, *iter_limit);
+        let c = run_prove(&parse::<cust::PropositionalLogic>(src), &cust::propositional_logic_rules(), *steps, *iter_limit);
+        compare(&mut diverged, &format!("prop_logic/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn calc_logic() {
+    use customlang::calc_logic as cust;
+    use symbollang::calc_logic as sym;
+    let mut diverged = vec![];
+    for (name, src, steps) in [("demorgan", inputs::demorgan(), 1), ("freges_theorem", inputs::freges_theorem(), 2)] {
+        let s = run_prove(&parse(&src), &sym::calc_logic_rules(), steps, 10);
+        let c = run_prove(&parse::<cust::CalcLogic>(&src), &cust::calc_logic_rules(), steps, 10);
+        compare(&mut diverged, &format!("calc_logic/{}", name), &s, &c);
+        let s = run_prove(&parse(&src), &sym::calculational_logic_rules(), steps, 10);
+        let c = run_prove(&parse::<cust::CalcLogic>(&src), &cust::calculational_logic_rules(), steps, 10);
+        compare(&mut diverged, &format!("calc_logic_fold/{}", name), &s, &c);
+        let s = run_prove(&parse(&src), &sym::calc_logic_sorted_rules(), steps, 10);
+        let c = run_prove(&parse::<cust::CalcLogic>(&src), &cust::calc_logic_sorted_rules(), steps, 10);
+        compare(&mut diverged, &format!("calc_logic_sorted/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn calculus() {
+    use customlang::calculus as cust;
+    use symbollang::calculus as sym;
+    let mut diverged = vec![];
+    for (name, src, _) in CALCULUS {
+        let s = run(&parse(src), &sym::calculus_rules(), 30, |_| AstSize);
+        let c = run(&parse::<cust::Math>(src), &cust::calculus_rules(), 30, |_| AstSize);
+        compare(&mut diverged, &format!("calculus/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn lambda() {
+    use customlang::lambda as cust;
+    use symbollang::lambda as sym;
+    let mut diverged = vec![];
+    for (name, src, _) in LAMBDA {
+        let s = run(&parse(src), &sym::lambda_rules(), 30, |_| AstSize);
+        let c = run(&parse::<cust::Lambda>(src), &cust::lambda_rules(), 30, |_| AstSize);
+        compare(&mut diverged, &format!("lambda/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn circuits() {
+    use customlang::circuits as cust;
+    use symbollang::circuits as sym;
+    let mut diverged = vec![];
+    for (name, src) in inputs::circuits() {
+        let s = run(&parse(&src), &sym::circuit_rules(), 10, |_| GateCost::area());
+        let c = run(&parse::<cust::Circuit>(&src), &cust::circuit_rules(), 10, |_| GateCost::area());
+        compare(&mut diverged, &format!("circuits/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn matrix() {
+    use customlang::matrix as cust;
+    use symbollang::matrix as sym;
+    let mut diverged = vec![];
+    for (name, src) in inputs::matrix() {
+        let s = run(&parse(&src), &sym::matrix_rules(), 30, Flops::new);
+        let c = run(&parse::<cust::Matrix>(&src), &cust::matrix_rules(), 30, Flops::new);
+        compare(&mut diverged, &format!("matrix/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn relational() {
+    use customlang::relational as cust;
+    use symbollang::relational as sym;
+    let mut diverged = vec![];
+    for (name, src) in inputs::relational() {
+        let s = run(&parse(&src), &sym::relational_rules(), 10, Work::new);
+        let c = run(&parse::<cust::Relation>(&src), &cust::relational_rules(), 10, Work::new);
+        compare(&mut diverged, &format!("relational/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn bitvector() {
+    use customlang::bitvector as cust;
+    use symbollang::bitvector as sym;
+    let mut diverged = vec![];
+    for (name, src) in inputs::bitvector() {
+        let s = run(&parse(&src), &sym::bitvector_rules(), 10, |_| bitvector::Latency);
+        let c = run(&parse::<cust::BitVec>(&src), &cust::bitvector_rules(), 10, |_| bitvector::Latency);
+        compare(&mut diverged, &format!("bitvector/{}", name), &s, &c);
+    }
+    assert_agree(diverged);
+}
+
+#[test]
+fn while_superinterpreter() {
+    use customlang::while_superinterpreter as cust;
+    use symbollang::while_superinterpreter as sym;
+    let mut diverged = vec![];
+    let s = run(&parse(WHILE_10), &sym::while_rules(), 100, |_| AstSize);
+    let c = run(&parse::<cust::WhileLanguage>(WHILE_10), &cust::while_rules(), 100, |_| AstSize);
+    compare(&mut diverged, "while_superinterpreter/while_10", &s, &c);
+    assert_agree(diverged);
+}
+
+// `derive` saturates an e-graph of facts, so only the number of paths and the
+// size of the e-graph are compared.
+#[test]
+fn reachability() {
+    use customlang::reachability as cust;
+    use symbollang::reachability as sym;
+    let mut diverged = vec![];
+    let theories = [
+        (sym::reachability_rules(), cust::reachability_rules()),
+        (sym::reachability_join_rules(), cust::reachability_join_rules()),
+    ];
+    for (sym_rules, cust_rules) in &theories {
+        for (name, facts) in inputs::reachability() {
+            let (sym_paths, s) = reachability::derive::<SymbolLang, ()>(&facts, sym_rules, 100);
+            let (cust_paths, c) = reachability::derive::<cust::Graph, ()>(&facts, cust_rules, 100);
+            if (sym_paths, s.num_classes, s.num_nodes) != (cust_paths, c.num_classes, c.num_nodes) {
+                diverged.push(format!("reachability/{}: {} paths, {} vs {} paths, {}", name, sym_paths, s, cust_paths, c));
+            }
+        }
+    }
+    assert_agree(diverged);
+}
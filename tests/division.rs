@@ -0,0 +1,51 @@
+use egg::*;
+use egg_benchmark::customlang::basic_maths::{basic_maths_division_rules, basic_maths_unchecked_division_rules, DivisionMath};
+use egg_benchmark::nonzero::*;
+use egg_benchmark::*;
+
+fn nonzero(src: &str) -> bool {
+    let expr: RecExpr<SymbolLang> = src.parse().unwrap();
+    let runner: Runner<SymbolLang, NonZero> = Runner::default().with_expr(&expr);
+    runner.egraph[runner.roots[0]].data
+}
+
+fn simplified(src: &str, rules: &Vec<Rewrite<DivisionMath, NonZero>>) -> String {
+    simplify(&src.parse().unwrap(), rules, 8).0.to_string()
+}
+
+#[test]
+fn nonzero_values_are_tracked() {
+    assert!(nonzero("(* 2 (^ (- 3) x))"));
+    assert!(nonzero("(/ (inv 4) 5)"));
+    assert!(!nonzero("(/ 4 x)"));
+    assert!(!nonzero("(+ 1 2)"));
+    assert!(!nonzero("(* 2 0)"));
+}
+
+#[test]
+fn division_needs_a_nonzero_operand() {
+    let rules = basic_maths_division_rules();
+    assert_eq!(simplified("(/ (* a 3) 3)", &rules), "a");
+    assert_eq!(simplified("(* (inv 2) 2)", &rules), "1");
+    assert_eq!(simplified("(inv (inv 5))", &rules), "5");
+    assert_eq!(simplified("(/ x x)", &rules), "(/ x x)");
+    assert_eq!(simplified("(/ (* a x) x)", &rules), "(/ (* a x) x)");
+    // 0^x is 0 only for non-zero x
+    assert_eq!(simplified("(^ 0 2)", &rules), "0");
+    assert_eq!(simplified("(^ 0 x)", &rules), "(^ 0 x)");
+
+    // without the checks, x / x is 1 even for x = 0
+    let unchecked = basic_maths_unchecked_division_rules();
+    assert_eq!(simplified("(/ x x)", &unchecked), "1");
+    assert_eq!(simplified("(/ (* a x) x)", &unchecked), "a");
+    assert_eq!(simplified("(^ 0 x)", &unchecked), "0");
+}
+
+#[test]
+fn division_is_evaluated_and_printed() {
+    let expr: RecExpr<DivisionMath> = "(/ a (* b (/ c d)))".parse().unwrap();
+    assert_eq!(to_julia(&expr), "a / (b * (c / d))");
+    assert_eq!(parse_julia::<DivisionMath>("a / (b * (c / d))").unwrap(), expr);
+    let env = [("a", 1), ("b", 2), ("c", 3), ("d", 4)].iter().map(|(v, n)| (v.to_string(), Rational::int(*n))).collect();
+    assert_eq!(eval_rational(&expr, &env), Rational::new(2, 3));
+}
@@ -0,0 +1,124 @@
+use egg::*;
+use egg_benchmark::customlang::basic_maths::BasicMath;
+use egg_benchmark::customlang::prop_logic::PropositionalLogic;
+use egg_benchmark::customlang::variadic_maths::VariadicMath;
+use egg_benchmark::{parse_julia, parse_julia_with, to_julia, Fold, JuliaParseError};
+
+fn sexp<L: FromOp + Language + std::fmt::Display>(src: &str) -> String {
+    parse_julia::<L>(src).unwrap().to_string()
+}
+
+#[test]
+fn prop_logic_inputs() {
+    assert_eq!(
+        sexp::<SymbolLang>(":(((p ⟹ q) && (r ⟹ s) && (p || r)) ⟹ (q || s))"),
+        "(=> (&& (&& (=> p q) (=> r s)) (|| p r)) (|| q s))"
+    );
+    assert_eq!(
+        sexp::<SymbolLang>("(p ⟹ (q ⟹ r)) ⟹ ((p ⟹ q) ⟹ (p ⟹ r))"),
+        "(=> (=> p (=> q r)) (=> (=> p q) (=> p r)))"
+    );
+    assert_eq!(
+        sexp::<SymbolLang>("!(p || q) == (!p && !q)"),
+        "(== (!! (|| p q)) (&& (!! p) (!! q)))"
+    );
+    assert_eq!(sexp::<SymbolLang>("p ⟹ q ⟹ r"), "(=> p (=> q r))");
+    assert_eq!(sexp::<SymbolLang>("p || q && r"), "(|| p (&& q r))");
+}
+
+#[test]
+fn basic_maths_inputs() {
+    assert_eq!(
+        sexp::<BasicMath>("a + b + (0 * c) + d"),
+        "(+ (+ (+ a b) (* 0 c)) d)"
+    );
+    assert_eq!(sexp::<BasicMath>("a + b * c ^ 2"), "(+ a (* b (^ c 2)))");
+    assert_eq!(sexp::<BasicMath>("x ^ y ^ z"), "(^ x (^ y z))");
+    assert_eq!(sexp::<BasicMath>("inv(x) * x ^ -1"), "(* (inv x) (^ x -1))");
+    assert_eq!(sexp::<BasicMath>("-x ^ 2 - y"), "(- (- (^ x 2)) y)");
+    assert_eq!(sexp::<VariadicMath>("f(x) + 1"), "(+ (call f x) 1)");
+}
+
+#[test]
+fn fold_direction() {
+    let parse = |src: &str, fold| parse_julia_with::<BasicMath>(src, fold).unwrap().to_string();
+    // simpl1 was translated nested to the right, simpl2 to the left.
+    assert_eq!(parse("a + b + (0 * c) + d", Fold::Right), "(+ a (+ b (+ (* 0 c) d)))");
+    assert_eq!(
+        parse("0 + (1 * foo * 0) + (a * 0) + a", Fold::Left),
+        "(+ (+ (+ 0 (* (* 1 foo) 0)) (* a 0)) a)"
+    );
+    assert_eq!(parse("1 * foo * 0", Fold::Right), "(* 1 (* foo 0))");
+    // Parentheses and other operators end a chain.
+    assert_eq!(parse("(a + b) + c", Fold::Right), "(+ (+ a b) c)");
+    assert_eq!(parse("a + b - c + d", Fold::Right), "(+ (- (+ a b) c) d)");
+    assert_eq!(
+        parse_julia_with::<SymbolLang>("p && q && r || s", Fold::Right).unwrap().to_string(),
+        "(|| (&& p (&& q r)) s)"
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        parse_julia::<SymbolLang>("p == q == r"),
+        Err(JuliaParseError::ChainedComparison)
+    );
+    assert_eq!(parse_julia::<SymbolLang>("(p || q"), Err(JuliaParseError::UnexpectedEnd));
+    assert_eq!(parse_julia::<SymbolLang>("p $ q"), Err(JuliaParseError::UnexpectedChar('$')));
+    assert_eq!(
+        parse_julia::<BasicMath>("p || q"),
+        Err(JuliaParseError::UnknownOp("||".to_string()))
+    );
+}
+
+#[test]
+fn infix_printing() {
+    let print = |s: &str| to_julia(&s.parse::<RecExpr<SymbolLang>>().unwrap());
+    assert_eq!(print("(|| (!! p) q)"), "!p || q");
+    assert_eq!(
+        print("(=> (&& (&& (=> p q) (=> r s)) (|| p r)) (|| q s))"),
+        "(p ⟹ q) && (r ⟹ s) && (p || r) ⟹ q || s"
+    );
+    assert_eq!(print("(!! (|| p q))"), "!(p || q)");
+    assert_eq!(print("(|| p (|| q r))"), "p || (q || r)");
+    assert_eq!(print("(- a (+ b c))"), "a - (b + c)");
+    assert_eq!(print("(^ (^ x y) z)"), "(x ^ y) ^ z");
+    assert_eq!(print("(^ x (- 1))"), "x ^ (-1)");
+    assert_eq!(print("(^ (- 1) x)"), "(-1) ^ x");
+    assert_eq!(print("(^ -1 x)"), "(-1) ^ x");
+    assert_eq!(print("(- (+ 2 x))"), "-(2 + x)");
+    assert_eq!(print("(- (* 2 x))"), "-(2 * x)");
+    assert_eq!(print("(== (== p q) r)"), "(p == q) == r");
+    assert_eq!(print("(call f x)"), "f(x)");
+    assert_eq!(print("(inv (+ x 1))"), "inv(x + 1)");
+}
+
+#[test]
+fn print_empty_expr() {
+    assert_eq!(to_julia(&RecExpr::<SymbolLang>::default()), "");
+}
+
+fn assert_roundtrip<L: FromOp + Language + std::fmt::Display>(s: &str) {
+    let expr: RecExpr<L> = s.parse().unwrap();
+    assert_eq!(parse_julia::<L>(&to_julia(&expr)).unwrap(), expr, "{}", s);
+}
+
+#[test]
+fn print_parse_roundtrip() {
+    for s in [
+        "(|| (!! (&& (|| (!! p) q) (&& (|| (!! r) s) (|| p r)))) (|| q s))",
+        "(== (!! (|| p q)) (&& (!! p) (!! q)))",
+    ] {
+        assert_roundtrip::<PropositionalLogic>(s);
+    }
+    for s in [
+        "(+ (+ (+ 0 (* (* 1 foo) 0)) (* a 0)) a)",
+        "(^ (* (- x) y) (^ z -1))",
+        "(- (- a b) (- (- c)))",
+        "(- (+ 2 x))",
+        "(- (* 2 x))",
+    ] {
+        assert_roundtrip::<BasicMath>(s);
+    }
+}
@@ -0,0 +1,53 @@
+use egg::*;
+use egg_benchmark::customlang::lambda::{lambda_rules, Lambda};
+use egg_benchmark::*;
+use egg_benchmark::{customlang, symbollang};
+
+const COMPOSE: &str = "
+    (let compose (lam f (lam g (lam x (app (var f) (app (var g) (var x))))))
+    (let add1 (lam y (+ (var y) 1))
+    (app (app (var compose) (var add1)) (var add1))))";
+
+const CHURCH_PLUS: &str = "
+    (let zero (lam f (lam x (var x)))
+    (let succ (lam n (lam f (lam x (app (var f) (app (app (var n) (var f)) (var x))))))
+    (let plus (lam m (lam n (lam f (lam x (app (app (var m) (var f)) (app (app (var n) (var f)) (var x)))))))
+    (app (app (app (app (var plus) (app (var succ) (var zero))) (app (var succ) (app (var succ) (var zero))))
+              (lam y (+ (var y) 1)))
+         0))))";
+
+fn proves(expr: &str, goal: &str) -> bool {
+    let (custom, _) = equivalent::<Lambda, _>(&expr.parse().unwrap(), &goal.parse().unwrap(), &lambda_rules(), 30);
+    let (symbol, _) = equivalent::<SymbolLang, _>(
+        &expr.parse().unwrap(), &goal.parse().unwrap(), &symbollang::lambda::lambda_rules(), 30);
+    assert_eq!(custom, symbol, "languages disagree on {} => {}", expr, goal);
+    custom
+}
+
+#[test]
+fn reduces_terms() {
+    assert!(proves("(lam x (+ 4 (app (lam y (var y)) 4)))", "(lam x 8)"));
+    assert!(proves("(if (= (var a) (var b)) (+ (var a) (var a)) (+ (var a) (var b)))", "(+ (var a) (var b))"));
+    assert!(proves("(let x 0 (let y 1 (+ (var x) (var y))))", "1"));
+    assert!(proves(COMPOSE, "(lam ?x (+ (var ?x) 2))"));
+    assert!(proves(CHURCH_PLUS, "3"));
+}
+
+#[test]
+fn avoids_capture() {
+    assert!(!proves("(let x 1 (lam x (var x)))", "(lam x 1)"));
+    assert!(!proves("(let y (+ (var x) (var x)) (lam x (var y)))", "(lam x (+ (var x) (var x)))"));
+    assert!(!proves(
+        "(let five 5 (let add-five (lam x (+ (var x) (var five))) (let five 6 (app (var add-five) 1))))",
+        "7",
+    ));
+}
+
+#[test]
+fn tracks_free_variables() {
+    let mut egraph: EGraph<Lambda, _> = EGraph::new(customlang::lambda::LambdaAnalysis);
+    let id = egraph.add_expr(&"(let y (var x) (lam x (app (var y) (var z))))".parse().unwrap());
+    let symbol = |s: &str| egraph.lookup(Lambda::Symbol(s.into())).unwrap();
+    let expected = [symbol("x"), symbol("z")].into_iter().collect();
+    assert_eq!(egraph[id].data.free, expected);
+}
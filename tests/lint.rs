@@ -0,0 +1,336 @@
+use egg::*;
+use egg_benchmark::customlang::basic_maths::BasicMath;
+use egg_benchmark::{customlang, symbollang};
+use egg_benchmark::{lint_rules, lint_rules_with_signature, Lint};
+use std::fmt::Display;
+
+fn lints(lints: Vec<Lint>) -> Vec<String> {
+    lints.into_iter().map(|l| l.to_string()).collect()
+}
+
+// Lints the custom language's rule set, and its SymbolLang copy against the
+// signature of the custom language.
+fn assert_lints<L, N, M>(cust: &[Rewrite<L, N>], sym: &[Rewrite<SymbolLang, M>], expected: &[&str])
+where
+    L: Language + Display + FromOp,
+    N: Analysis<L>,
+    M: Analysis<SymbolLang>,
+{
+    assert_eq!(lints(lint_rules(cust)), expected);
+    assert_eq!(lints(lint_rules_with_signature::<_, _, L>(sym)), expected);
+}
+
+#[test]
+fn prop_logic_rules() {
+    let expected = [
+        // `<=>` on a commutativity rule adds a reverse rule that is the same rule.
+        "or-2-rev: alpha-equivalent to or-2",
+        "and-2-rev: alpha-equivalent to and-2",
+        // Double negation introduction, which the proofs need to reach `true`.
+        "neg-3-rev: rhs is larger than lhs",
+        // De Morgan and distributivity grow in one direction by design.
+        "comb-1: rhs is larger than lhs",
+        "comb-2: rhs is larger than lhs",
+        "comb-3: rhs is larger than lhs",
+        "comb-4: rhs is larger than lhs",
+        // `==` and `=>` are rewritten into `&&`, `||` and `!!`, which is larger.
+        "impl-3: rhs is larger than lhs",
+        "impl-4: rhs is larger than lhs",
+    ];
+    assert_lints(
+        &customlang::prop_logic::propositional_logic_rules(),
+        &symbollang::prop_logic::propositional_logic_rules(),
+        &expected,
+    );
+}
+
+#[test]
+fn calc_logic_rules() {
+    let expected = [
+        "==-sym-rev: alpha-equivalent to ==-sym",
+        // `!=`, `&&` and `=>` are defined in terms of `==`, `||` and `!!`.
+        "!=: rhs is larger than lhs",
+        "||-sym-rev: alpha-equivalent to ||-sym",
+        // Distributivity and De Morgan grow in one direction by design.
+        "||-distr: rhs is larger than lhs",
+        "||-demorgan: rhs is larger than lhs",
+        "!&&: rhs is larger than lhs",
+        // The definitions of `&&` and `=>`, continued.
+        "&&: rhs is larger than lhs",
+        "=>: rhs is larger than lhs",
+    ];
+    assert_lints(&customlang::calc_logic::calc_logic_rules(), &symbollang::calc_logic::calc_logic_rules(), &expected);
+}
+
+#[test]
+fn basic_maths_rules() {
+    let expected = [
+        // Distributivity of `*` over `+`, and of `^` over `+` and `*`.
+        "distr-abc: rhs is larger than lhs",
+        "power-ynym-2: rhs is larger than lhs",
+        "power-distr-1: rhs is larger than lhs",
+        // `inv` is expressed as a power, so that the power rules apply to it.
+        "power-inv: rhs is larger than lhs",
+    ];
+    assert_lints(&customlang::basic_maths::basic_maths_rules(), &symbollang::basic_maths::basic_maths_rules(), &expected);
+}
+
+#[test]
+fn basic_maths_sorted_rules() {
+    // the basic_maths rules, with the same lints
+    assert_lints(
+        &customlang::basic_maths::basic_maths_sorted_rules(),
+        &symbollang::basic_maths::basic_maths_sorted_rules(),
+        &[
+            "distr-abc: rhs is larger than lhs",
+            "power-ynym-2: rhs is larger than lhs",
+            "power-distr-1: rhs is larger than lhs",
+            "power-inv: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn basic_maths_division_rules() {
+    let expected = [
+        "distr-abc: rhs is larger than lhs",
+        "power-ynym-2: rhs is larger than lhs",
+        "power-distr-1: rhs is larger than lhs",
+        // `/` is expressed as a product with `inv`, and `inv` as a power.
+        "div-mul-inv: rhs is larger than lhs",
+        "power-inv: rhs is larger than lhs",
+    ];
+    assert_lints(
+        &customlang::basic_maths::basic_maths_division_rules(),
+        &symbollang::basic_maths::basic_maths_division_rules(),
+        &expected,
+    );
+    assert_lints(
+        &customlang::basic_maths::basic_maths_unchecked_division_rules(),
+        &symbollang::basic_maths::basic_maths_unchecked_division_rules(),
+        &expected,
+    );
+}
+
+#[test]
+fn variadic_maths_rules() {
+    assert_lints(
+        &customlang::variadic_maths::variadic_maths_rules(),
+        &symbollang::variadic_maths::variadic_maths_rules(),
+        &[
+            // Splitting a call into a nested binary one, so that the binary
+            // rules apply at every arity.
+            "split-add-3: rhs is larger than lhs",
+            "split-add-4: rhs is larger than lhs",
+            "split-mul-3: rhs is larger than lhs",
+            "split-mul-4: rhs is larger than lhs",
+            // The basic_maths rules.
+            "distr-abc: rhs is larger than lhs",
+            "power-ynym-2: rhs is larger than lhs",
+            "power-distr-1: rhs is larger than lhs",
+            "power-inv: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn calc_logic_fold_and_sorted_rules() {
+    // the calc_logic rules, with the same lints; the folding rules have none
+    let expected = [
+        "==-sym-rev: alpha-equivalent to ==-sym",
+        "!=: rhs is larger than lhs",
+        "||-sym-rev: alpha-equivalent to ||-sym",
+        "||-distr: rhs is larger than lhs",
+        "||-demorgan: rhs is larger than lhs",
+        "!&&: rhs is larger than lhs",
+        "&&: rhs is larger than lhs",
+        "=>: rhs is larger than lhs",
+    ];
+    assert_lints(
+        &customlang::calc_logic::calculational_logic_rules(),
+        &symbollang::calc_logic::calculational_logic_rules(),
+        &expected,
+    );
+    assert_lints(
+        &customlang::calc_logic::calc_logic_sorted_rules(),
+        &symbollang::calc_logic::calc_logic_sorted_rules(),
+        &expected,
+    );
+}
+
+#[test]
+fn calculus_rules() {
+    assert_lints(
+        &customlang::calculus::calculus_rules(),
+        &symbollang::calculus::calculus_rules(),
+        &[
+            // `-` and `/` are expressed with `+`, `*` and `pow`.
+            "sub-canon: rhs is larger than lhs",
+            "div-canon: rhs is larger than lhs",
+            "distribute: rhs is larger than lhs",
+            // The derivative rules, which take a term apart.
+            "d-add: rhs is larger than lhs",
+            "d-mul: rhs is larger than lhs",
+            "d-sin: rhs is larger than lhs",
+            "d-cos: rhs is larger than lhs",
+            "d-ln: rhs is larger than lhs",
+            "d-power: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn lambda_rules() {
+    assert_lints(
+        &customlang::lambda::lambda_rules(),
+        &symbollang::lambda::lambda_rules(),
+        &[
+            // Unfolding `fix` and pushing a `let` into the children of a node.
+            "fix: rhs is larger than lhs",
+            "let-app: rhs is larger than lhs",
+            "let-add: rhs is larger than lhs",
+            "let-eq: rhs is larger than lhs",
+            "let-if: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn circuit_rules() {
+    // Every rule is one-directional or keeps the size of the circuit.
+    assert_lints(&customlang::circuits::circuit_rules(), &symbollang::circuits::circuit_rules(), &[]);
+}
+
+#[test]
+fn matrix_rules() {
+    assert_lints(
+        &customlang::matrix::matrix_rules(),
+        &symbollang::matrix::matrix_rules(),
+        &[
+            // Distributing `*`, `transpose` and `scale`; the reverse rules factor.
+            "mul-add-distr: rhs is larger than lhs",
+            "add-mul-distr: rhs is larger than lhs",
+            "transpose-mul: rhs is larger than lhs",
+            "transpose-add: rhs is larger than lhs",
+            "scale-add: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn relational_rules() {
+    assert_lints(
+        &customlang::relational::relational_rules(),
+        &symbollang::relational::relational_rules(),
+        &[
+            // Pulling a join predicate out and pushing operators into a union.
+            "join-select: rhs is larger than lhs",
+            "select-union: rhs is larger than lhs",
+            "project-union: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn bitvector_rules() {
+    assert_lints(
+        &customlang::bitvector::bitvector_rules(),
+        &symbollang::bitvector::bitvector_rules(),
+        &[
+            // Subtraction as addition of the negation.
+            "sub-neg: rhs is larger than lhs",
+            // The expanding halves of De Morgan and of distributivity over
+            // `*`, `&` and `<<`, whose reverse rules factor.
+            "demorgan-and: rhs is larger than lhs",
+            "demorgan-or: rhs is larger than lhs",
+            "mul-add-distr: rhs is larger than lhs",
+            "and-or-distr: rhs is larger than lhs",
+            "and-xor-distr: rhs is larger than lhs",
+            "shl-add-distr: rhs is larger than lhs",
+            // A shift as a multiplication, which the constant folding finishes.
+            "shl-mul: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn while_rules() {
+    assert_lints(
+        &customlang::while_superinterpreter::while_rules(),
+        &symbollang::while_superinterpreter::while_rules(),
+        &[
+            // `eval` is pushed into the children of a node.
+            "eval-add: rhs is larger than lhs",
+            "eval-mul: rhs is larger than lhs",
+            "eval-sub: rhs is larger than lhs",
+            "eval-lt: rhs is larger than lhs",
+            "eval-or: rhs is larger than lhs",
+            "eval-and: rhs is larger than lhs",
+            "eval-while: rhs is larger than lhs",
+        ],
+    );
+}
+
+#[test]
+fn reachability_rules() {
+    // `path-trans` is a multi-pattern in one and has a custom applier in the
+    // other, so only `path-base` is checked.
+    assert_lints(
+        &customlang::reachability::reachability_rules(),
+        &symbollang::reachability::reachability_rules(),
+        &[],
+    );
+    assert_lints(
+        &customlang::reachability::reachability_join_rules(),
+        &symbollang::reachability::reachability_join_rules(),
+        &[],
+    );
+}
+
+// Forwards to a pattern but does not declare its variables, so egg cannot
+// reject unbound variables when the rewrite is built.
+struct Unchecked(Pattern<SymbolLang>);
+
+impl Applier<SymbolLang, ()> for Unchecked {
+    fn apply_one(
+        &self,
+        egraph: &mut EGraph<SymbolLang, ()>,
+        eclass: Id,
+        subst: &Subst,
+        searcher_ast: Option<&PatternAst<SymbolLang>>,
+        rule_name: Symbol,
+    ) -> Vec<Id> {
+        self.0.apply_one(egraph, eclass, subst, searcher_ast, rule_name)
+    }
+
+    fn get_pattern_ast(&self) -> Option<&PatternAst<SymbolLang>> {
+        Applier::<SymbolLang, ()>::get_pattern_ast(&self.0)
+    }
+}
+
+#[test]
+fn detects_each_lint() {
+    let unbound = Unchecked("(* ?a ?b)".parse().unwrap());
+    let rules: Vec<Rewrite<SymbolLang, ()>> = vec![
+        Rewrite::new("unbound", "(+ ?a 0)".parse::<Pattern<_>>().unwrap(), unbound).unwrap(),
+        rewrite!("commute"; "(+ ?a ?b)" => "(+ ?b ?a)"),
+        rewrite!("commute-copy"; "(+ ?a ?b)" => "(+ ?b ?a)"),
+        rewrite!("commute-renamed"; "(+ ?x ?y)" => "(+ ?y ?x)"),
+        rewrite!("commute-zero"; "(+ 0 ?a)" => "(+ ?a 0)"),
+        rewrite!("expand"; "(* ?a 2)" => "(+ ?a (* 1 ?a))"),
+        rewrite!("typo"; "(inv ?a ?b)" => "(* ?a ?b)"),
+    ];
+    let lints: Vec<String> =
+        lint_rules_with_signature::<_, _, BasicMath>(&rules).iter().map(|l| l.to_string()).collect();
+    assert_eq!(
+        lints,
+        [
+            "unbound: ?b is not bound by the lhs",
+            "commute-copy: duplicate of commute",
+            "commute-renamed: alpha-equivalent to commute",
+            "commute-zero: subsumed by bidirectional commute",
+            "expand: rhs is larger than lhs",
+            "typo: lhs uses inv with arity 2, which never matches",
+        ]
+    );
+}
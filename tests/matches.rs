@@ -24,3 +24,132 @@ fn two_matches() {
     assert_eq!(2, rule.searcher.n_matches(&egraph));
 // println!("{:?}", rule.search(&egraph));
 }
+
+struct Case {
+    exprs: &'static [&'static str],
+    unions: &'static [(&'static str, &'static str)],
+    pattern: &'static str,
+    // "root | ?var = term, ..." for every match, with the variables sorted
+    matches: &'static [&'static str],
+}
+
+// E-matching cases, each with every match it must produce.
+const CASES: [Case; 12] = [
+    // associativity, as in two_matches
+    Case {
+        exprs: &["0", "1", "foo", "(* 1 foo)", "(* foo 1)"],
+        unions: &[("foo", "(* 1 foo)"), ("foo", "(* foo 1)")],
+        pattern: "(* (* ?a ?b) ?c)",
+        matches: &["foo | ?a = 1, ?b = foo, ?c = 1", "foo | ?a = foo, ?b = 1, ?c = 1"],
+    },
+    // a ground pattern
+    Case { exprs: &["(+ a b)", "(+ b a)"], unions: &[], pattern: "(+ a b)", matches: &["(+ a b) | "] },
+    // a lone variable, which matches every e-class
+    Case { exprs: &["(+ a b)"], unions: &[], pattern: "?x", matches: &["(+ a b) | ?x = (+ a b)", "a | ?x = a", "b | ?x = b"] },
+    // commutativity, on an e-class with both orders
+    Case {
+        exprs: &["(+ a b)", "(+ b a)"],
+        unions: &[("(+ a b)", "(+ b a)")],
+        pattern: "(+ ?a ?b)",
+        matches: &["(+ a b) | ?a = a, ?b = b", "(+ a b) | ?a = b, ?b = a"],
+    },
+    // a non-linear pattern
+    Case {
+        exprs: &["(|| p p)", "(|| p q)", "(|| q r)"],
+        unions: &[],
+        pattern: "(|| ?p ?p)",
+        matches: &["(|| p p) | ?p = p"],
+    },
+    // a non-linear pattern whose arguments are equal only after a union
+    Case {
+        exprs: &["(|| p p)", "(|| p q)", "(|| q r)"],
+        unions: &[("q", "r")],
+        pattern: "(|| ?p ?p)",
+        matches: &["(|| p p) | ?p = p", "(|| q q) | ?p = q"],
+    },
+    // a variable repeated at different depths
+    Case {
+        exprs: &["(f a (g a))", "(f a (g b))"],
+        unions: &[],
+        pattern: "(f ?x (g ?x))",
+        matches: &["(f a (g a)) | ?x = a"],
+    },
+    // a non-linear pattern with nested subpatterns
+    Case {
+        exprs: &["(+ (* x y) (* x z))", "(+ (* x y) (* w z))"],
+        unions: &[],
+        pattern: "(+ (* ?a ?b) (* ?a ?c))",
+        matches: &["(+ (* x y) (* x z)) | ?a = x, ?b = y, ?c = z"],
+    },
+    // a pattern with a literal
+    Case { exprs: &["(* x 0)", "(* 0 x)"], unions: &[], pattern: "(* ?a 0)", matches: &["(* x 0) | ?a = x"] },
+    // a nested pattern on the cyclic e-class a = f(a)
+    Case { exprs: &["(f a)"], unions: &[("a", "(f a)")], pattern: "(f (f ?x))", matches: &["a | ?x = a"] },
+    // a pattern with a subpattern below a cycle
+    Case {
+        exprs: &["(g a b)", "(f a)"],
+        unions: &[("a", "(f a)")],
+        pattern: "(g (f ?x) ?y)",
+        matches: &["(g a b) | ?x = a, ?y = b"],
+    },
+    // a pattern where the cycle a = f(a, b) makes a a match of itself
+    Case {
+        exprs: &["(f a b)", "(f b a)"],
+        unions: &[("a", "(f a b)")],
+        pattern: "(f ?x ?y)",
+        matches: &["(f b a) | ?x = b, ?y = a", "a | ?x = a, ?y = b"],
+    },
+];
+
+// The smallest term of the e-class `id`, breaking ties by the printed term so
+// that the expectations do not depend on the order of the nodes.
+fn smallest(egraph: &EGraph<SymbolLang, ()>, extractor: &Extractor<AstSize, SymbolLang, ()>, id: Id) -> String {
+    let cost = extractor.find_best_cost(id);
+    egraph[id]
+        .nodes
+        .iter()
+        .filter(|n| n.children.iter().map(|c| extractor.find_best_cost(*c)).sum::<usize>() + 1 == cost)
+        .map(|n| match n.children.as_slice() {
+            [] => n.to_string(),
+            children => {
+                let children: Vec<String> = children.iter().map(|c| smallest(egraph, extractor, *c)).collect();
+                format!("({} {})", n, children.join(" "))
+            }
+        })
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn table() {
+    for case in CASES {
+        let mut egraph = EGraph::<SymbolLang, ()>::default();
+        for expr in case.exprs {
+            egraph.add_expr(&expr.parse().unwrap());
+        }
+        for (a, b) in case.unions {
+            let a = egraph.add_expr(&a.parse().unwrap());
+            let b = egraph.add_expr(&b.parse().unwrap());
+            egraph.union(a, b);
+        }
+        egraph.rebuild();
+
+        let pattern: Pattern<SymbolLang> = case.pattern.parse().unwrap();
+        let extractor = Extractor::new(&egraph, AstSize);
+        let mut matches: Vec<String> = vec![];
+        for m in pattern.search(&egraph) {
+            for subst in &m.substs {
+                let mut vars: Vec<String> = pattern
+                    .vars()
+                    .iter()
+                    .map(|v| format!("{} = {}", v, smallest(&egraph, &extractor, subst[*v])))
+                    .collect();
+                vars.sort();
+                matches.push(format!("{} | {}", smallest(&egraph, &extractor, m.eclass), vars.join(", ")));
+            }
+        }
+        matches.sort();
+        assert_eq!(matches, case.matches, "{}", case.pattern);
+        assert_eq!(pattern.n_matches(&egraph), case.matches.len(), "{}", case.pattern);
+    }
+}
@@ -0,0 +1,36 @@
+use egg::*;
+use egg_benchmark::customlang::matrix::{matrix_rules, Dimensions, Matrix};
+use egg_benchmark::matrix::*;
+use egg_benchmark::*;
+
+#[test]
+fn dimensions_are_tracked() {
+    let expr: RecExpr<Matrix> = "(transpose (scale s (* (mat A 2 3) (mat B 3 5))))".parse().unwrap();
+    let runner: Runner<Matrix, Dimensions> = Runner::default().with_expr(&expr);
+    assert_eq!(runner.egraph[runner.roots[0]].data, Some(Shape::Matrix(5, 2)));
+
+    let ill_typed: RecExpr<Matrix> = "(* (mat A 2 3) (mat B 2 3))".parse().unwrap();
+    let runner: Runner<Matrix, Dimensions> = Runner::default().with_expr(&ill_typed);
+    assert_eq!(runner.egraph[runner.roots[0]].data, None);
+}
+
+#[test]
+fn chains_reach_the_optimal_bracketing() {
+    // the textbook example, best bracketing ((A0 (A1 A2)) ((A3 A4) A5))
+    assert_eq!(matrix_chain_flops(&chain_dims(6)), 15125);
+    for n in [3, 6, 8] {
+        let dims = chain_dims(n);
+        let expr: RecExpr<Matrix> = matrix_chain(&dims).parse().unwrap();
+        let (best, (before, after), _) = optimize(&expr, &matrix_rules(), 30, Flops::new);
+        assert!(after <= before);
+        assert_eq!(after, matrix_chain_flops(&dims), "{} is not optimal", best);
+    }
+}
+
+#[test]
+fn scaling_moves_to_the_smallest_matrix() {
+    // scaling the 50 x 50 outer product costs 2500, scaling A costs 50
+    let expr: RecExpr<Matrix> = "(scale s (* (mat A 50 1) (mat B 1 50)))".parse().unwrap();
+    let (best, cost, _) = optimize(&expr, &matrix_rules(), 30, Flops::new);
+    assert_eq!(cost, (5000, 2550), "{}", best);
+}
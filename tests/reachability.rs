@@ -0,0 +1,28 @@
+use egg_benchmark::customlang::reachability::{reachability_join_rules, reachability_rules};
+use egg_benchmark::reachability::*;
+use egg_benchmark::*;
+
+#[test]
+fn derives_the_transitive_closure() {
+    for (facts, paths) in [(chain(6), 15), (cycle(5), 25), (grid(3), 27)] {
+        assert_eq!(derive(&facts, &reachability_rules(), 100).0, paths, "{:?}", facts);
+        assert_eq!(derive(&facts, &symbollang::reachability::reachability_rules(), 100).0, paths, "{:?}", facts);
+    }
+}
+
+#[test]
+fn multi_patterns_agree_with_joins() {
+    for facts in [chain(8), cycle(4), grid(4)] {
+        let (paths, size) = derive(&facts, &reachability_rules(), 100);
+        let (join_paths, join_size) = derive(&facts, &reachability_join_rules(), 100);
+        assert_eq!(paths, join_paths);
+        assert_eq!((size.num_classes, size.num_nodes), (join_size.num_classes, join_size.num_nodes));
+    }
+}
+
+#[test]
+fn iteration_limit_bounds_path_length() {
+    // every iteration extends the paths by one edge
+    let (paths, _) = derive(&chain(10), &reachability_rules(), 2);
+    assert_eq!(paths, 9 + 8);
+}
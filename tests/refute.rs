@@ -0,0 +1,61 @@
+use egg::*;
+use egg_benchmark::customlang::prop_logic::{propositional_logic_rules, PropositionalLogic};
+use egg_benchmark::*;
+
+fn goal(s: &str) -> RecExpr<PropositionalLogic> {
+    s.parse().unwrap()
+}
+
+#[test]
+fn reports_counterexample() {
+    let tru = goal("true");
+    let (res, _) = prove_or_refute(&goal("(=> (|| p q) (&& p q))"), &propositional_logic_rules(), 1, 4, &tru);
+    assert_eq!(res.to_string(), "not a tautology, false for p = true, q = false");
+}
+
+#[test]
+fn reports_out_of_budget() {
+    let tru = goal("true");
+    let frege = goal("(=> (=> p (=> q r)) (=> (=> p q) (=> p r)))");
+    let (res, _) = prove_or_refute(&frege, &propositional_logic_rules(), 1, 1, &tru);
+    assert!(matches!(res, ProofResult::OutOfBudget(_)), "{}", res);
+    let (res, _) = prove_or_refute(&frege, &propositional_logic_rules(), 1, 10, &tru);
+    assert!(matches!(res, ProofResult::Proved), "{}", res);
+}
+
+#[test]
+fn reports_unknown_for_non_propositional_goals() {
+    let rules = symbollang::prop_logic::propositional_logic_rules();
+    let tru: RecExpr<SymbolLang> = "true".parse().unwrap();
+    // `<` is not propositional, so the goal can be neither refuted nor
+    // confirmed to be a tautology, small or large.
+    let small: RecExpr<SymbolLang> = "(=> (< x y) (&& p (< x y)))".parse().unwrap();
+    assert_eq!(falsify(&small), Err(Unsupported));
+    let (res, _) = prove_or_refute(&small, &rules, 1, 2, &tru);
+    assert!(matches!(res, ProofResult::Unknown(_)), "{}", res);
+
+    let large: RecExpr<SymbolLang> =
+        format!("(|| (< x y) {})", chain("||", EXHAUSTIVE_VAR_LIMIT + 1, |i| format!("x{}", i))).parse().unwrap();
+    assert_eq!(falsify(&large), Err(Unsupported));
+}
+
+// Chains `op` over `(f i)` for i in 0..n, nested to the right.
+fn chain(op: &str, n: usize, f: impl Fn(usize) -> String) -> String {
+    (0..n - 1).rev().fold(f(n - 1), |acc, i| format!("({} {} {})", op, f(i), acc))
+}
+
+#[test]
+fn dpll_on_many_variables() {
+    let n = EXHAUSTIVE_VAR_LIMIT + 8;
+    // x0 => x1 => ... is falsified only by making every premise true
+    let implications = goal(&chain("=>", n, |i| format!("x{}", i)));
+    let assignment = falsify(&implications).unwrap().unwrap();
+    assert_eq!(eval_bool(&implications, &assignment), Some(false));
+    assert!((0..n - 1).all(|i| assignment[&format!("x{}", i)]));
+
+    let excluded_middle = goal(&chain("&&", n, |i| format!("(|| x{} (!! x{}))", i, i)));
+    assert_eq!(falsify(&excluded_middle), Ok(None));
+
+    let xor = goal(&chain("==", n, |i| format!("x{}", i)));
+    assert_eq!(eval_bool(&xor, &falsify(&xor).unwrap().unwrap()), Some(false));
+}
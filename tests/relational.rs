@@ -0,0 +1,38 @@
+use egg::*;
+use egg_benchmark::customlang::relational::{relational_rules, Relation, Statistics};
+use egg_benchmark::relational::*;
+use egg_benchmark::*;
+
+fn data(src: &str) -> RelData {
+    let expr: RecExpr<Relation> = src.parse().unwrap();
+    let runner: Runner<Relation, Statistics> = Runner::default().with_expr(&expr);
+    runner.egraph[runner.roots[0]].data.clone()
+}
+
+#[test]
+fn statistics_are_estimated() {
+    let d = data("(select (and (= R.a S.a) (< R.b 10)) (join true (table R 300) (table S 20)))");
+    assert_eq!(d.tables.iter().collect::<Vec<_>>(), ["R", "S"]);
+    assert_eq!(d.estimate.map(f64::round), Some(200.0));
+    assert_eq!(data("(project (cols R.a) (union (table R 30) (table R 12)))").estimate, Some(42.0));
+}
+
+#[test]
+fn selections_are_pushed_into_joins() {
+    let expr: RecExpr<Relation> = "(select (and (= R.a S.a) (< R.b 10)) (join true (table R 3000) (table S 20)))"
+        .parse()
+        .unwrap();
+    let (best, (before, after), _) = optimize(&expr, &relational_rules(), 10, Work::new);
+    assert!(after < before, "{} did not improve", best);
+    assert!(best.to_string().contains("(select (< R.b 10) (table R 3000))"), "{} filters late", best);
+    assert!(!best.to_string().contains("join true"), "{} keeps a cross product", best);
+}
+
+#[test]
+fn generated_queries_improve() {
+    for n in 2..=4 {
+        let expr: RecExpr<Relation> = query(n).parse().unwrap();
+        let (best, (before, after), _) = optimize(&expr, &relational_rules(), 10, Work::new);
+        assert!(after < before, "{} did not improve on {}", best, expr);
+    }
+}
@@ -0,0 +1,295 @@
+// Snapshots of the final e-graph size and stop reason of every benchmark input
+// in both languages, with the drivers and parameters of the benchmarks, so that
+// a new version of egg or an edit to a rule set cannot silently change the
+// numbers in the comparison. The snapshots are in tests/snapshots, one file
+// per theory with the names of the benchmarks; rerun with UPDATE_SNAPSHOTS=1
+// to rewrite them after an intended change.
+//
+// The runners get no time limit, which the debug build would hit at
+// different iterations from run to run.
+
+use egg::*;
+use egg_benchmark::inputs::{self, PROP_LOGIC_FAMILIES};
+use egg_benchmark::reachability::derive_runner;
+use egg_benchmark::variadic::flatten;
+use egg_benchmark::*;
+use std::fmt::Display;
+use std::time::Duration;
+use std::{env, fs};
+
+const NO_TIME_LIMIT: Duration = Duration::from_secs(3600);
+
+fn parse<L: FromOp>(src: &str) -> RecExpr<L> {
+    src.parse().unwrap()
+}
+
+// The line of the benchmark `name`, with the sizes summed over `runners` as
+// `prove` does, and their stop reasons.
+fn record<L: Language, N: Analysis<L>>(name: &str, runners: &[Runner<L, N>]) -> String {
+    let size = EGraphSize {
+        num_classes: runners.iter().map(|r| r.egraph.classes().count()).sum(),
+        num_memo: runners.iter().map(|r| r.egraph.total_size()).sum(),
+        num_nodes: runners.iter().map(|r| r.egraph.total_number_of_nodes()).sum(),
+    };
+    let reasons: Vec<String> = runners.iter().map(|r| format!("{:?}", r.stop_reason.as_ref().unwrap())).collect();
+    format!("{} {}, stop: {}", name, size, reasons.join("; "))
+}
+
+// `simplify` and `optimize`, which only differ in the extraction.
+fn simplified<L, N>(name: &str, expr: &RecExpr<L>, rules: &[Rewrite<L, N>], timeout: usize) -> String
+where
+    L: Language,
+    N: Analysis<L> + Default,
+{
+    let runner = simplify_runner(timeout).with_time_limit(NO_TIME_LIMIT).with_expr(expr).run(rules);
+    record(name, &[runner])
+}
+
+fn equivalent<L, N>(name: &str, expr: &RecExpr<L>, goal: &str, rules: &[Rewrite<L, N>], timeout: usize) -> String
+where
+    L: Language + FromOp + 'static,
+    N: Analysis<L> + Default,
+{
+    let goal: Pattern<L> = goal.parse().unwrap();
+    let runner = equivalent_runner(expr, &goal, timeout).with_time_limit(NO_TIME_LIMIT).run(rules);
+    record(name, &[runner])
+}
+
+fn proved<L, N>(name: &str, expr: &RecExpr<L>, rules: &Vec<Rewrite<L, N>>, steps: usize, timeout: usize) -> String
+where
+    L: Language + FromOp,
+    N: Analysis<L> + Default,
+{
+    let tru: RecExpr<L> = parse("true");
+    let (_, runners) = prove_runs(expr, rules, steps, &tru, || prove_runner(timeout).with_time_limit(NO_TIME_LIMIT));
+    record(name, &runners)
+}
+
+// Compares `lines` with the snapshot of `theory`, or rewrites the snapshot.
+fn check(theory: &str, lines: Vec<String>) {
+    let path = format!("{}/tests/snapshots/{}.txt", env!("CARGO_MANIFEST_DIR"), theory);
+    if env::var("UPDATE_SNAPSHOTS").is_ok() {
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        return;
+    }
+    let snapshot = fs::read_to_string(&path).unwrap_or_default();
+    let expected: Vec<&str> = snapshot.lines().collect();
+    if lines == expected {
+        return;
+    }
+    let mut changes: Vec<String> = vec![];
+    for i in 0..lines.len().max(expected.len()) {
+        let (old, new) = (expected.get(i).copied(), lines.get(i).map(String::as_str));
+        if old != new {
+            changes.extend(old.map(|line| format!("- {}", line)));
+            changes.extend(new.map(|line| format!("+ {}", line)));
+        }
+    }
+    panic!("{} differs from the snapshot {}:\n{}", theory, path, changes.join("\n"));
+}
+
+fn basic_maths_lines<L, N, M, D>(lang: &str, rules: &[Rewrite<L, ()>], sorted: &[Rewrite<L, N>], division: [(&str, Vec<Rewrite<M, D>>); 2]) -> Vec<String>
+where
+    L: Language + FromOp,
+    N: Analysis<L> + Default,
+    M: Language + FromOp,
+    D: Analysis<M> + Default,
+{
+    let mut lines = vec![];
+    for (name, src) in inputs::maths() {
+        lines.push(simplified(&format!("{}/basic_maths/{}", lang, name), &parse(&src), rules, 8));
+        lines.push(simplified(&format!("{}/basic_maths_sorted/{}", lang, name), &parse(&src), sorted, 8));
+    }
+    for (theory, rules) in &division {
+        for (name, src) in inputs::DIVISION {
+            lines.push(simplified(&format!("{}/{}/{}", lang, theory, name), &parse(src), rules, 8));
+        }
+    }
+    lines
+}
+
+#[test]
+fn basic_maths() {
+    use customlang::basic_maths as cust;
+    use symbollang::basic_maths as sym;
+    let mut lines = basic_maths_lines(
+        "symbollang",
+        &sym::basic_maths_rules(),
+        &sym::basic_maths_sorted_rules(),
+        [
+            ("basic_maths_division", sym::basic_maths_division_rules()),
+            ("basic_maths_division_unchecked", sym::basic_maths_unchecked_division_rules()),
+        ],
+    );
+    lines.extend(basic_maths_lines(
+        "customlang",
+        &cust::basic_maths_rules(),
+        &cust::basic_maths_sorted_rules(),
+        [
+            ("basic_maths_division", cust::basic_maths_division_rules()),
+            ("basic_maths_division_unchecked", cust::basic_maths_unchecked_division_rules()),
+        ],
+    ));
+    check("basic_maths", lines);
+}
+
+fn variadic_maths_lines<L: Language + Display + FromOp, N: Analysis<L> + Default>(lang: &str, rules: &[Rewrite<L, N>]) -> Vec<String> {
+    inputs::maths()
+        .iter()
+        .map(|(name, src)| simplified(&format!("{}/variadic_maths/{}", lang, name), &flatten(&parse(src)), rules, 8))
+        .collect()
+}
+
+#[test]
+fn variadic_maths() {
+    let mut lines = variadic_maths_lines("symbollang", &symbollang::variadic_maths::variadic_maths_rules());
+    lines.extend(variadic_maths_lines("customlang", &customlang::variadic_maths::variadic_maths_rules()));
+    check("variadic_maths", lines);
+}
+
+fn prop_logic_lines<L: Language + FromOp, N: Analysis<L> + Default>(lang: &str, rules: &Vec<Rewrite<L, N>>) -> Vec<String> {
+    let mut lines = vec![
+        proved(&format!("{}/prop_logic/prove1", lang), &parse(&inputs::prove1()), rules, 2, 6),
+        proved(&format!("{}/prop_logic/demorgan", lang), &parse(&inputs::demorgan()), rules, 1, 10),
+        proved(&format!("{}/prop_logic/freges_theorem", lang), &parse(&inputs::freges_theorem()), rules, 1, 10),
+    ];
+    // every n up to the largest the benchmark tries
+    for (family, generate, max_n) in PROP_LOGIC_FAMILIES {
+        for n in 1..=max_n {
+            lines.push(proved(&format!("{}/prop_logic/{}_{}", lang, family, n), &parse(&generate(n)), rules, 1, 10));
+        }
+    }
+    lines
+}
+
+#[test]
+fn prop_logic() {
+    let mut lines = prop_logic_lines("symbollang", &symbollang::prop_logic::propositional_logic_rules());
+    lines.extend(prop_logic_lines("customlang", &customlang::prop_logic::propositional_logic_rules()));
+    check("prop_logic", lines);
+}
+
+fn calc_logic_lines<L: Language + FromOp, N: Analysis<L> + Default>(lang: &str, theory: &str, rules: &Vec<Rewrite<L, N>>) -> Vec<String> {
+    vec![
+        proved(&format!("{}/{}/demorgan", lang, theory), &parse(&inputs::demorgan()), rules, 1, 10),
+        proved(&format!("{}/{}/freges_theorem", lang, theory), &parse(&inputs::freges_theorem()), rules, 2, 10),
+    ]
+}
+
+#[test]
+fn calc_logic() {
+    use customlang::calc_logic as cust;
+    use symbollang::calc_logic as sym;
+    let mut lines = calc_logic_lines("symbollang", "calc_logic", &sym::calc_logic_rules());
+    lines.extend(calc_logic_lines("symbollang", "calc_logic_fold", &sym::calculational_logic_rules()));
+    lines.extend(calc_logic_lines("symbollang", "calc_logic_sorted", &sym::calc_logic_sorted_rules()));
+    lines.extend(calc_logic_lines("customlang", "calc_logic", &cust::calc_logic_rules()));
+    lines.extend(calc_logic_lines("customlang", "calc_logic_fold", &cust::calculational_logic_rules()));
+    lines.extend(calc_logic_lines("customlang", "calc_logic_sorted", &cust::calc_logic_sorted_rules()));
+    check("calc_logic", lines);
+}
+
+fn calculus_lines<L: Language + FromOp + 'static, N: Analysis<L> + Default>(lang: &str, rules: &[Rewrite<L, N>]) -> Vec<String> {
+    inputs::CALCULUS
+        .iter()
+        .map(|(name, expr, goal)| equivalent(&format!("{}/calculus/{}", lang, name), &parse(expr), goal, rules, 30))
+        .collect()
+}
+
+#[test]
+fn calculus() {
+    let mut lines = calculus_lines("symbollang", &symbollang::calculus::calculus_rules());
+    lines.extend(calculus_lines("customlang", &customlang::calculus::calculus_rules()));
+    check("calculus", lines);
+}
+
+fn lambda_lines<L: Language + FromOp + 'static, N: Analysis<L> + Default>(lang: &str, rules: &[Rewrite<L, N>]) -> Vec<String> {
+    inputs::LAMBDA
+        .iter()
+        .map(|(name, expr, goal)| equivalent(&format!("{}/lambda/{}", lang, name), &parse(expr), goal, rules, 30))
+        .collect()
+}
+
+#[test]
+fn lambda() {
+    let mut lines = lambda_lines("symbollang", &symbollang::lambda::lambda_rules());
+    lines.extend(lambda_lines("customlang", &customlang::lambda::lambda_rules()));
+    check("lambda", lines);
+}
+
+// The inputs of a theory whose benchmarks run `optimize` or `simplify`.
+fn optimize_lines<L, N>(lang: &str, theory: &str, inputs: &[(String, String)], rules: &[Rewrite<L, N>], timeout: usize) -> Vec<String>
+where
+    L: Language + FromOp,
+    N: Analysis<L> + Default,
+{
+    inputs
+        .iter()
+        .map(|(name, src)| simplified(&format!("{}/{}/{}", lang, theory, name), &parse(src), rules, timeout))
+        .collect()
+}
+
+#[test]
+fn circuits() {
+    let inputs = inputs::circuits();
+    let mut lines = optimize_lines("symbollang", "circuits", &inputs, &symbollang::circuits::circuit_rules(), 10);
+    lines.extend(optimize_lines("customlang", "circuits", &inputs, &customlang::circuits::circuit_rules(), 10));
+    check("circuits", lines);
+}
+
+#[test]
+fn matrix() {
+    let inputs = inputs::matrix();
+    let mut lines = optimize_lines("symbollang", "matrix", &inputs, &symbollang::matrix::matrix_rules(), 30);
+    lines.extend(optimize_lines("customlang", "matrix", &inputs, &customlang::matrix::matrix_rules(), 30));
+    check("matrix", lines);
+}
+
+#[test]
+fn relational() {
+    let inputs = inputs::relational();
+    let mut lines = optimize_lines("symbollang", "relational", &inputs, &symbollang::relational::relational_rules(), 10);
+    lines.extend(optimize_lines("customlang", "relational", &inputs, &customlang::relational::relational_rules(), 10));
+    check("relational", lines);
+}
+
+#[test]
+fn bitvector() {
+    let inputs = inputs::bitvector();
+    let mut lines = optimize_lines("symbollang", "bitvector", &inputs, &symbollang::bitvector::bitvector_rules(), 10);
+    lines.extend(optimize_lines("customlang", "bitvector", &inputs, &customlang::bitvector::bitvector_rules(), 10));
+    check("bitvector", lines);
+}
+
+#[test]
+fn while_superinterpreter() {
+    let inputs = [("while_10".to_string(), inputs::WHILE_10.to_string())];
+    let mut lines = optimize_lines("symbollang", "while_superinterpreter", &inputs, &symbollang::while_superinterpreter::while_rules(), 100);
+    lines.extend(optimize_lines("customlang", "while_superinterpreter", &inputs, &customlang::while_superinterpreter::while_rules(), 100));
+    check("while_superinterpreter", lines);
+}
+
+fn reachability_lines<L, N>(lang: &str, theory: &str, rules: &[Rewrite<L, N>]) -> Vec<String>
+where
+    L: Language + FromOp,
+    N: Analysis<L> + Default,
+{
+    let mut lines = vec![];
+    for (name, facts) in inputs::reachability() {
+        let (runner, _) = derive_runner(&facts, 100);
+        let runner = runner.with_time_limit(NO_TIME_LIMIT).run(rules);
+        lines.push(record(&format!("{}/{}/{}", lang, theory, name), &[runner]));
+    }
+    lines
+}
+
+#[test]
+fn reachability() {
+    use customlang::reachability as cust;
+    use symbollang::reachability as sym;
+    let mut lines = reachability_lines("symbollang", "reachability", &sym::reachability_rules());
+    lines.extend(reachability_lines("symbollang", "reachability_join", &sym::reachability_join_rules()));
+    lines.extend(reachability_lines("customlang", "reachability", &cust::reachability_rules()));
+    lines.extend(reachability_lines("customlang", "reachability_join", &cust::reachability_join_rules()));
+    check("reachability", lines);
+}
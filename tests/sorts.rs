@@ -0,0 +1,71 @@
+use egg::*;
+use egg_benchmark::customlang::basic_maths::{basic_maths_rules, basic_maths_sorted_rules, BasicMath};
+use egg_benchmark::customlang::calc_logic::{calc_logic_rules, calc_logic_sorted_rules, CalcLogic};
+use egg_benchmark::sorts::*;
+use egg_benchmark::*;
+
+fn sort(src: &str) -> Sort {
+    let expr: RecExpr<SymbolLang> = src.parse().unwrap();
+    let runner: Runner<SymbolLang, Sorts> = Runner::default().with_expr(&expr);
+    runner.egraph[runner.roots[0]].data
+}
+
+#[test]
+fn sorts_are_inferred() {
+    assert_eq!(sort("(+ 1 (* 2 (- 3)))"), Sort::Int);
+    assert_eq!(sort("(== (+ 1 2) x)"), Sort::Bool);
+    assert_eq!(sort("(!! false)"), Sort::Bool);
+    assert_eq!(sort("(+ 1 x)"), Sort::Expr);
+    assert_eq!(sort("(^ 2 3)"), Sort::Expr);
+    assert_eq!(sort("(* 2 true)"), Sort::Expr);
+    assert!(Sort::Expr.may_be(Sort::Bool) && !Sort::Int.may_be(Sort::Bool));
+}
+
+#[test]
+fn variables_learn_their_sort() {
+    let rules: Vec<Rewrite<SymbolLang, Sorts>> = vec![rewrite!("x-is-1"; "x" => "1")];
+    let expr: RecExpr<SymbolLang> = "(+ x 2)".parse().unwrap();
+    let runner = Runner::default().with_expr(&expr).run(&rules);
+    assert_eq!(runner.egraph[runner.roots[0]].data, Sort::Int);
+}
+
+#[test]
+fn guards_reject_ill_sorted_matches() {
+    let rules: Vec<Rewrite<SymbolLang, Sorts>> = vec![
+        rewrite!("fold-or"; "(|| ?p ?p)" => "?p" if is_sort("?p", Sort::Bool)),
+        rewrite!("or-idem"; "(|| ?p ?p)" => "?p" if may_be_sort("?p", Sort::Bool)),
+    ];
+    let simplified = |src: &str| simplify(&src.parse().unwrap(), &rules, 4).0.to_string();
+    assert_eq!(simplified("(|| (!! p) (!! p))"), "(!! p)");
+    assert_eq!(simplified("(|| p p)"), "p");
+    assert_eq!(simplified("(|| 1 1)"), "(|| 1 1)");
+}
+
+#[test]
+fn basic_maths_keeps_booleans() {
+    for (src, unsorted, sorted) in [
+        ("(+ true 0)", "true", "(+ true 0)"),
+        ("(* (* 1 true) b)", "(* true b)", "(* b (* 1 true))"),
+        ("(+ a (* 0 b))", "a", "a"),
+    ] {
+        let expr: RecExpr<BasicMath> = src.parse().unwrap();
+        assert_eq!(simplify(&expr, &basic_maths_rules(), 8).0.to_string(), unsorted);
+        let (best, _) = simplify(&expr, &basic_maths_sorted_rules(), 8);
+        assert_eq!(AstSize.cost_rec(&best), AstSize.cost_rec(&sorted.parse::<RecExpr<BasicMath>>().unwrap()), "{}", best);
+    }
+}
+
+#[test]
+fn calc_logic_proves_the_same_goals() {
+    let tru: RecExpr<CalcLogic> = "true".parse().unwrap();
+    for (goal, steps) in [
+        ("(== (!! (|| p q)) (&& (!! p) (!! q)))", 1),
+        ("(=> (=> p (=> q r)) (=> (=> p q) (=> p r)))", 2),
+    ] {
+        let goal: RecExpr<CalcLogic> = goal.parse().unwrap();
+        assert_eq!(prove(&goal, &calc_logic_sorted_rules(), steps, 10, &tru).0, tru);
+    }
+    let ill_sorted: RecExpr<CalcLogic> = "(|| 1 1)".parse().unwrap();
+    assert_eq!(prove(&ill_sorted, &calc_logic_rules(), 1, 10, &tru).0.to_string(), "1");
+    assert_eq!(prove(&ill_sorted, &calc_logic_sorted_rules(), 1, 10, &tru).0.to_string(), "(|| 1 1)");
+}
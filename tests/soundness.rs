@@ -0,0 +1,209 @@
+use egg::*;
+use egg_benchmark::customlang::basic_maths::BasicMath;
+use egg_benchmark::customlang::calc_logic::{calc_logic_rules, calculational_logic_rules, CalcLogic};
+use egg_benchmark::customlang::prop_logic::{propositional_logic_rules, PropositionalLogic};
+use egg_benchmark::fold::fold_bool;
+use egg_benchmark::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+// Checks that both sides of every rule agree under all assignments of the
+// rule's pattern variables.
+fn assert_sound<L: Language + Display>(rules: &[Rewrite<L, ()>]) {
+    for rule in rules {
+        let lhs = rule.searcher.get_pattern_ast().unwrap();
+        let rhs = rule.applier.get_pattern_ast().unwrap();
+        let mut vars = bool_vars(lhs);
+        for v in bool_vars(rhs) {
+            if !vars.contains(&v) {
+                vars.push(v);
+            }
+        }
+        for env in bool_assignments(&vars) {
+            let l = eval_bool(lhs, &env);
+            let r = eval_bool(rhs, &env);
+            assert!(l.is_some() && l == r, "{} is unsound under {:?}", rule.name, env);
+        }
+    }
+}
+
+fn assert_proofs_are_tautologies<L: Language + FromOp + Display>(
+    rules: &Vec<Rewrite<L, ()>>,
+    goals: &[(&str, usize, usize)],
+) {
+    let tru: RecExpr<L> = "true".parse().unwrap();
+    for (goal, steps, timeout) in goals {
+        let expr: RecExpr<L> = goal.parse().unwrap();
+        let (res, _) = prove(&expr, rules, *steps, *timeout, &tru);
+        assert_eq!(res, tru, "{} not proved", goal);
+        assert!(is_tautology(&expr), "{} was proved but is not a tautology", goal);
+    }
+}
+
+#[test]
+fn prop_logic_rules_are_sound() {
+    assert_sound(&propositional_logic_rules());
+}
+
+#[test]
+fn calc_logic_rules_are_sound() {
+    assert_sound(&calc_logic_rules());
+}
+
+#[test]
+fn circuit_rules_are_sound() {
+    assert_sound(&customlang::circuits::circuit_rules());
+    assert_sound(&symbollang::circuits::circuit_rules());
+}
+
+#[test]
+fn prop_logic_proofs_are_tautologies() {
+    assert_proofs_are_tautologies::<PropositionalLogic>(
+        &propositional_logic_rules(),
+        &[
+            ("(|| (!! (&& (|| (!! p) q) (&& (|| (!! r) s) (|| p r)))) (|| q s))", 2, 6),
+            ("(== (!! (|| p q)) (&& (!! p) (!! q)))", 1, 10),
+            ("(=> (=> p (=> q r)) (=> (=> p q) (=> p r)))", 1, 10),
+        ],
+    );
+}
+
+#[test]
+fn calc_logic_proofs_are_tautologies() {
+    assert_proofs_are_tautologies::<CalcLogic>(
+        &calc_logic_rules(),
+        &[
+            ("(== (!! (|| p q)) (&& (!! p) (!! q)))", 1, 10),
+            ("(=> (=> p (=> q r)) (=> (=> p q) (=> p r)))", 2, 10),
+        ],
+    );
+}
+
+#[test]
+fn fold_agrees_with_evaluator() {
+    for op in ["==", "||", "=>", "&&"] {
+        let expr: RecExpr<CalcLogic> = format!("({} p q)", op).parse().unwrap();
+        for p in [true, false] {
+            for q in [true, false] {
+                let env = [("p".to_string(), p), ("q".to_string(), q)].into_iter().collect();
+                assert_eq!(fold_bool(op, &[p, q]), eval_bool(&expr, &env), "{} {} {}", op, p, q);
+            }
+        }
+    }
+    assert_eq!(fold_bool("!!", &[true]), Some(false));
+}
+
+#[test]
+fn calculational_logic_folds_literals() {
+    let expr: RecExpr<CalcLogic> = "(=> (|| false (!! true)) (&& true false))".parse().unwrap();
+    assert_eq!(simplify(&expr, &calculational_logic_rules(), 10).0, "true".parse().unwrap());
+    assert_proofs_are_tautologies::<CalcLogic>(
+        &calculational_logic_rules(),
+        &[
+            ("(== (!! (|| p q)) (&& (!! p) (!! q)))", 1, 10),
+            ("(=> (=> p (=> q r)) (=> (=> p q) (=> p r)))", 2, 10),
+        ],
+    );
+}
+
+#[test]
+fn evaluator() {
+    let expr: RecExpr<SymbolLang> = "(=> p (|| q false))".parse().unwrap();
+    let env = |p, q| [("p".to_string(), p), ("q".to_string(), q)].into_iter().collect();
+    assert_eq!(eval_bool(&expr, &env(true, false)), Some(false));
+    assert_eq!(eval_bool(&expr, &env(false, false)), Some(true));
+    assert_eq!(bool_vars(&expr), ["p", "q"]);
+    assert!(!is_tautology(&expr));
+    assert!(is_tautology(&"(|| p (!! p))".parse::<RecExpr<SymbolLang>>().unwrap()));
+}
+
+fn random_env(vars: &[String], rng: &mut StdRng) -> HashMap<String, Rational> {
+    vars.iter().map(|v| (v.clone(), Rational::int(rng.gen_range(-3..=3)))).collect()
+}
+
+// Returns the names of the rules for which some random assignment gives both
+// sides a value and the values differ.
+fn unsound_numeric_rules<L: Language + Display>(rules: &[Rewrite<L, ()>]) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut unsound = vec![];
+    for rule in rules {
+        let lhs = rule.searcher.get_pattern_ast().unwrap();
+        let rhs = rule.applier.get_pattern_ast().unwrap();
+        let mut vars = num_vars(lhs);
+        for v in num_vars(rhs) {
+            if !vars.contains(&v) {
+                vars.push(v);
+            }
+        }
+        for _ in 0..500 {
+            let env = random_env(&vars, &mut rng);
+            if let (Some(l), Some(r)) = (eval_rational(lhs, &env), eval_rational(rhs, &env)) {
+                if l != r {
+                    unsound.push(rule.name.to_string());
+                    break;
+                }
+            }
+        }
+    }
+    unsound
+}
+
+#[test]
+fn basic_maths_rules_preserve_value() {
+    // 0^x --> 0 is wrong for x = 0, where Julia (and the evaluator) give 1.
+    assert_eq!(unsound_numeric_rules(&customlang::basic_maths::basic_maths_rules()), ["power-0x"]);
+    assert_eq!(unsound_numeric_rules(&symbollang::basic_maths::basic_maths_rules()), ["power-0x"]);
+}
+
+#[test]
+fn variadic_maths_rules_preserve_value() {
+    // the same exception as basic_maths, at every arity of the other rules
+    assert_eq!(unsound_numeric_rules(&customlang::variadic_maths::variadic_maths_rules()), ["power-0x"]);
+    assert_eq!(unsound_numeric_rules(&symbollang::variadic_maths::variadic_maths_rules()), ["power-0x"]);
+}
+
+#[test]
+fn basic_maths_simplify_preserves_value() {
+    let rules = customlang::basic_maths::basic_maths_rules();
+    let mut rng = StdRng::seed_from_u64(0);
+    for input in [
+        "(+ a (+ b (+ (* 0 c) d)))",
+        "(+ (+ (+ 0 (* (* 1 foo) 0)) (* a 0)) a)",
+        "(* (^ x 2) (^ x 3))",
+        "(+ (* 2 (+ a b)) (- a a))",
+    ] {
+        let expr: RecExpr<BasicMath> = input.parse().unwrap();
+        let (res, _) = simplify(&expr, &rules, 8);
+        let vars = num_vars(&expr);
+        for _ in 0..100 {
+            let env = random_env(&vars, &mut rng);
+            assert_eq!(eval_rational(&expr, &env), eval_rational(&res, &env), "{} => {}", input, res);
+        }
+    }
+}
+
+#[test]
+fn rational_evaluator() {
+    let expr: RecExpr<SymbolLang> = "(+ (inv x) (^ y (- 2)))".parse().unwrap();
+    let env = |x, y| {
+        [("x".to_string(), Rational::int(x)), ("y".to_string(), Rational::int(y))].into_iter().collect()
+    };
+    assert_eq!(eval_rational(&expr, &env(2, 2)), Rational::new(3, 4));
+    assert_eq!(eval_rational(&expr, &env(0, 2)), None);
+    let zero_pow_zero: RecExpr<SymbolLang> = "(^ 0 0)".parse().unwrap();
+    assert_eq!(eval_rational(&zero_pow_zero, &HashMap::new()), Some(Rational::int(1)));
+}
+
+#[test]
+fn rational_ordering_does_not_overflow() {
+    let r = |num, den| Rational::new(num, den).unwrap();
+    let max = i128::MAX;
+    assert!(r(max, max - 1) > Rational::int(1));
+    assert!(r(max - 1, max) < Rational::int(1));
+    assert!(r(max, max - 1) < r(max - 1, max - 2));
+    assert!(r(-max, max - 1) < r(-(max - 1), max));
+    assert_eq!(r(max, 2).cmp(&r(max, 2)), std::cmp::Ordering::Equal);
+    assert!(r(1, 3) < r(1, 2) && r(-1, 2) < r(-1, 3));
+}
@@ -0,0 +1,35 @@
+use egg::*;
+use egg_benchmark::customlang::prop_logic::{propositional_logic_rules, PropositionalLogic};
+use egg_benchmark::tautologies::*;
+use egg_benchmark::*;
+
+const FAMILIES: [fn(usize) -> String; 4] = [frege_chain, distributivity_tower, pigeonhole, syllogism_chain];
+
+#[test]
+fn families_are_tautologies() {
+    for family in FAMILIES {
+        for n in 1..=3 {
+            let goal: RecExpr<PropositionalLogic> = family(n).parse().unwrap();
+            assert!(is_tautology(&goal), "{} is not a tautology", goal);
+        }
+    }
+    assert_eq!(frege_chain(1), "(=> (=> p (=> q1 r)) (=> (=> p q1) (=> p r)))");
+    assert_eq!(syllogism_chain(2), "(=> (&& (=> x0 x1) (=> x1 x2)) (=> x0 x2))");
+    assert_eq!(bool_vars::<PropositionalLogic>(&pigeonhole(3).parse().unwrap()).len(), 12);
+}
+
+#[test]
+fn sweeps_stop_within_limits() {
+    for family in FAMILIES {
+        let largest = largest_proved(family, &propositional_logic_rules(), 1, 10, 4);
+        assert!(largest >= 1, "{} is not proved", family(1));
+    }
+    // the budget bounds how far a family gets
+    let rules = propositional_logic_rules();
+    assert!(largest_proved(syllogism_chain, &rules, 1, 1, 12) < largest_proved(syllogism_chain, &rules, 1, 10, 12));
+    assert_eq!(largest_proved(pigeonhole, &rules, 1, 0, 4), 0);
+    assert_eq!(
+        largest_proved(frege_chain, &rules, 1, 10, 8),
+        largest_proved(frege_chain, &symbollang::prop_logic::propositional_logic_rules(), 1, 10, 8)
+    );
+}
@@ -0,0 +1,46 @@
+use egg::*;
+use egg_benchmark::customlang::prop_logic::PropositionalLogic;
+use egg_benchmark::{customlang, symbollang, *};
+
+const EX_ORIG: &str = "(=> (&& (&& (=> p q) (=> r s)) (|| p r)) (|| q s))";
+
+#[test]
+fn rewrites_implications_away() {
+    let expected = "(|| (!! (&& (&& (|| (!! p) q) (|| (!! r) s)) (|| p r))) (|| q s))";
+
+    let ex: RecExpr<SymbolLang> = EX_ORIG.parse().unwrap();
+    let (result, steps) = rewrite_fixpoint(&ex, &symbollang::prop_logic::propositional_logic_rules(), 100);
+    assert_eq!(result, expected.parse().unwrap());
+    assert_eq!(steps, 3);
+
+    let ex: RecExpr<PropositionalLogic> = EX_ORIG.parse().unwrap();
+    let (result, steps) = rewrite_fixpoint(&ex, &customlang::prop_logic::propositional_logic_rules(), 100);
+    assert_eq!(result, expected.parse().unwrap());
+    assert_eq!(steps, 3);
+}
+
+#[test]
+fn rewrites_to_fixpoint() {
+    let rules = customlang::prop_logic::propositional_logic_rules();
+    let cases = [
+        // or-3 needs both sides to be equal, and-4 only applies once they are
+        ("(&& (|| (&& a true) a) true)", "a"),
+        // impl-2 after the inner or-5 has fired
+        ("(== (|| p false) p)", "true"),
+        // only equational rules match, so nothing changes
+        ("(|| q p)", "(|| q p)"),
+    ];
+    for (input, expected) in cases {
+        let ex: RecExpr<PropositionalLogic> = input.parse().unwrap();
+        let (result, _) = rewrite_fixpoint(&ex, &rules, 100);
+        assert_eq!(result, expected.parse().unwrap(), "{}", input);
+    }
+}
+
+#[test]
+fn stops_after_max_steps() {
+    let ex: RecExpr<PropositionalLogic> = EX_ORIG.parse().unwrap();
+    let (result, steps) = rewrite_fixpoint(&ex, &customlang::prop_logic::propositional_logic_rules(), 1);
+    assert_eq!(steps, 1);
+    assert_eq!(result, "(=> (&& (&& (|| (!! p) q) (=> r s)) (|| p r)) (|| q s))".parse().unwrap());
+}
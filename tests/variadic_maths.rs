@@ -0,0 +1,45 @@
+use egg::*;
+use egg_benchmark::customlang::basic_maths::{basic_maths_rules, BasicMath};
+use egg_benchmark::customlang::variadic_maths::{variadic_maths_rules, VariadicMath};
+use egg_benchmark::variadic::*;
+use egg_benchmark::*;
+
+fn flat(s: &str) -> RecExpr<VariadicMath> {
+    flatten(&s.parse().unwrap())
+}
+
+#[test]
+fn flattens_nested_calls() {
+    assert_eq!(flat("(+ a (+ b (+ (* 0 c) d)))").to_string(), "(+ a b (* 0 c) d)");
+    assert_eq!(flat("(* (* 1 foo) (- (+ a b) (+ c d)))").to_string(), "(* 1 foo (- (+ a b) (+ c d)))");
+    assert_eq!(flat("(+ (- (+ a b)) c)").to_string(), "(+ (- (+ a b)) c)");
+    // the flattened term has no leftover nodes
+    assert_eq!(flat("(+ (+ a b) (+ c d))").as_ref().len(), 5);
+    // calls are nested to the right beyond the largest arity the rules match
+    assert_eq!(flat("(+ a (+ b (+ c (+ d e))))").to_string(), "(+ a b c (+ d e))");
+    assert_eq!(
+        flat("(* (* (* (* (* (* (* (* a b) c) d) e) f) g) h) i)").to_string(),
+        "(* a b c (* d e f (* g h i)))"
+    );
+}
+
+#[test]
+fn rules_cover_every_arity() {
+    let rules = variadic_maths_rules();
+    assert!(rules.iter().any(|r| r.name.as_str() == "commute-add-4-2"));
+    assert!(rules.iter().any(|r| r.name.as_str() == "split-mul-4"));
+}
+
+#[test]
+fn simplifies_like_the_binary_encoding() {
+    for input in ["(+ (+ (+ 0 (* (* 1 foo) 0)) (* a 0)) a)", "(* (+ a (+ b 0)) (* 1 c))"] {
+        let binary: RecExpr<BasicMath> = input.parse().unwrap();
+        let (best, _) = simplify(&binary, &basic_maths_rules(), 8);
+        let (flat_best, _) = simplify(&flat(input), &variadic_maths_rules(), 8);
+        assert_eq!(AstSize.cost_rec(&flat_best), AstSize.cost_rec(&best), "{} vs {}", flat_best, best);
+
+        let symbols: RecExpr<SymbolLang> = flatten(&input.parse().unwrap());
+        let (sym_best, _) = simplify(&symbols, &symbollang::variadic_maths::variadic_maths_rules(), 8);
+        assert_eq!(sym_best.to_string(), flat_best.to_string());
+    }
+}
@@ -0,0 +1,29 @@
+use egg::*;
+use egg_benchmark::{customlang, simplify, symbollang};
+
+use customlang::while_superinterpreter::WhileLanguage;
+
+fn interpret_cust(program: &str) -> RecExpr<WhileLanguage> {
+    let expr: RecExpr<WhileLanguage> = program.parse().unwrap();
+    simplify(&expr, &customlang::while_superinterpreter::while_rules(), 100).0
+}
+
+fn interpret_sym(program: &str) -> RecExpr<SymbolLang> {
+    let expr: RecExpr<SymbolLang> = program.parse().unwrap();
+    simplify(&expr, &symbollang::while_superinterpreter::while_rules(), 100).0
+}
+
+#[test]
+fn interprets_programs() {
+    let programs = [
+        ("(eval (seq (= x 0) (seq (while (< x 10) (= x (+ x 1))) x)) {x=0})", "10"),
+        ("(eval (seq (= y (* x 3)) (- y x)) {x=2})", "4"),
+        ("(eval (if (&& (< x 1) (!! (< 0 x))) (= x 5) skip) {x=0,y=1})", "{x=5,y=1}"),
+        ("(eval (if (|| (< x 0) false) (= x 5) skip) {x=0})", "{x=0}"),
+        ("(eval (seq (= f 1) (seq (while (< 1 n) (seq (= f (* f n)) (= n (- n 1)))) f)) {n=5})", "120"),
+    ];
+    for (program, expected) in programs {
+        assert_eq!(interpret_cust(program), expected.parse().unwrap(), "{}", program);
+        assert_eq!(interpret_sym(program), expected.parse().unwrap(), "{}", program);
+    }
+}